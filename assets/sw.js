@@ -4,6 +4,10 @@ var filesToCache = [
   './index.html',
   './quiz.js',
   './quiz_bg.wasm',
+  './manifest.json',
+  './favicon.ico',
+  './assets/icon-256.png',
+  './assets/icon_ios_touch_192.png',
 ];
 
 /* Start the service worker and cache all of the app's content */
@@ -0,0 +1,31 @@
+use crate::images::ImageCache;
+use crate::quiz_core::Question;
+use crate::tags::tag_chips;
+use egui::RichText;
+
+/// Every question with its hints and answer fully expanded, e.g. to check an answer key
+/// before printing it.
+pub fn answer_sheet_ui(ui: &mut egui::Ui, questions: &[Question], images: &mut ImageCache) {
+    egui::ScrollArea::vertical()
+        .auto_shrink(false)
+        .show(ui, |ui| {
+            for (nr, question) in questions.iter().enumerate() {
+                ui.label(RichText::new(format!("{nr}. {}", question.question)).strong());
+                if let Some(image) = &question.image {
+                    images.show(ui, image);
+                }
+                tag_chips(ui, &question.tags);
+                for (nr, hint) in [&question.hint1, &question.hint2].into_iter().enumerate() {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label(format!("Hinweis {}:", nr + 1));
+                        images.show_text_or_image(ui, hint);
+                    });
+                }
+                ui.label(format!("Antwort: {}", question.answers_text()));
+                if let Some(source) = &question.source {
+                    ui.label(RichText::new(format!("Quelle: {source}")).small().weak());
+                }
+                ui.separator();
+            }
+        });
+}
@@ -1,46 +1,476 @@
-use egui::{Context, RichText};
+use crate::answer_sheet::answer_sheet_ui;
+use crate::diff::QuizDiff;
+use crate::edit::edit_question_ui;
+use crate::exam::Exam;
+use crate::export::{anki_text, ExportOptions};
+use crate::find_replace::FindReplace;
+use crate::flashcard::flashcard_ui;
+use crate::images::ImageCache;
+use crate::import::{load_questions, ImportOptions, QuizFormat};
+use crate::jump::JumpSearch;
+use crate::keys::KeyBindings;
+use crate::last_results::LastResults;
+use crate::playlist::{Playlist, PlaylistAction, PlaylistEntry};
+use crate::qr::qr_code_ui;
+use crate::quiz_core::{Question, Quiz, Show, TagFilter};
+use crate::reveal_counts::RevealCounts;
+use crate::screenshot::png_of_region;
+use crate::session::SessionSnapshot;
+use crate::shuffle::{shuffle, shuffle_spread_topics};
+use crate::slideshow::SlideshowConfig;
+use crate::stats::QuizStats;
+use crate::tags::{tag_breadcrumbs, tag_chips, tag_levels};
+use crate::toasts::Toasts;
+use egui::{Color32, Context, Key, KeyboardShortcut, Modifiers, RichText};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::BTreeSet;
 use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::path::PathBuf;
 use std::sync::mpsc::{channel, Receiver, Sender};
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant, SystemTime};
+
+const OPEN_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::O);
+
+const EDIT_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::E);
+
+/// egui's own selection color in the dark theme, which the color picker starts from.
+const DEFAULT_ACCENT_COLOR: Color32 = Color32::from_rgb(0, 92, 128);
+
+/// Seconds the answer stays on screen after a time limit ran out, before advancing.
+const TIME_LIMIT_ANSWER_SECS: f64 = 5.0;
+
+/// Version of the persisted [`MyApp`] state. Bump it when a change needs more than
+/// `#[serde(default)]` to read older states, and add the upgrade to [`MyApp::migrate`].
+const SCHEMA_VERSION: u32 = 3;
+
+/// The budget when "Aufdeckbudget" is turned on, see [`MyApp::reveal_budget`].
+const DEFAULT_REVEAL_BUDGET: u32 = 10;
+
+/// A small quiz embedded in the binary, so the app can be tried out offline. It is loaded on
+/// the first start, when there is no saved state yet.
+const EXAMPLE_QUIZ: &str = include_str!("../assets/example_quiz.json");
+
+/// Small quizzes to start authoring from, as `(menu entry, file name, content)`.
+const TEMPLATES: [(&str, &str, &str); 3] = [
+    (
+        "Vokabeln",
+        "Vokabeln.json",
+        include_str!("../assets/templates/vokabeln.json"),
+    ),
+    (
+        "Allgemeinwissen",
+        "Allgemeinwissen.json",
+        include_str!("../assets/templates/allgemeinwissen.json"),
+    ),
+    (
+        "Mehrere richtige Antworten",
+        "Mehrere richtige Antworten.json",
+        include_str!("../assets/templates/mehrere_antworten.json"),
+    ),
+];
+
+/// What happens to the reveal state when navigating to another question.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+enum RevealMode {
+    /// Start from the default reveal state on every question.
+    #[default]
+    Reset,
+    /// Restore what was revealed when the question was last shown.
+    Remember,
+}
+
+/// What `>>` does on the last question, unless the playlist continues with another file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+enum EndBehavior {
+    /// Stay on the last question.
+    #[default]
+    Stop,
+    /// Start over with the first question.
+    Loop,
+    /// Show a summary of the quiz.
+    Summary,
+}
+
+/// Where the question text is placed horizontally.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+enum QuestionAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Question {
-    pub question: String,
-    pub hint1: String,
-    pub hint2: String,
-    pub answer: String,
+impl QuestionAlign {
+    fn layout(self) -> egui::Layout {
+        let align = match self {
+            QuestionAlign::Left => egui::Align::Min,
+            QuestionAlign::Center => egui::Align::Center,
+            QuestionAlign::Right => egui::Align::Max,
+        };
+        egui::Layout::top_down(align)
+    }
+}
+
+/// Messages from the background tasks that read quiz files.
+enum FileMsg {
+    /// A file was read completely.
+    Loaded {
+        name: String,
+        bytes: Vec<u8>,
+        /// Where the file lives on disk. Always `None` on wasm.
+        path: Option<PathBuf>,
+        /// Set when the file is played from the playlist.
+        playlist_entry: Option<usize>,
+        /// Stay on the current question instead of starting over, e.g. when reloading.
+        keep_position: bool,
+        /// The [`MyApp::load_generation`] of the load. Only the load started last is kept.
+        generation: u64,
+    },
+    /// A file to compare the loaded quiz against was read.
+    Compare { name: String, bytes: Vec<u8> },
+    /// A file with a [`SessionSnapshot`] was read. `generation` is as for [`FileMsg::Loaded`].
+    Session {
+        name: String,
+        bytes: Vec<u8>,
+        generation: u64,
+    },
+    /// A file was written, e.g. by an export.
+    Saved(String),
+    /// The quiz itself was saved as JSON, so there are no unsaved changes left.
+    QuizSaved(String),
+    /// A file could not be written.
+    SaveError(String),
+    /// A file could not be read.
+    Error(String),
+    /// A file was picked and is being read, for the load of this [`MyApp::load_generation`].
+    Reading(u64),
+}
+
+/// The quiz before the last file load or batch edit, so it can be undone.
+struct StashedQuiz {
+    /// Empty if no quiz was loaded.
+    questions: Vec<Question>,
+    question_nr: usize,
+    playlist_entry: Option<usize>,
+    #[cfg(not(target_arch = "wasm32"))]
+    source: Option<SourceFile>,
 }
 
-#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
-pub struct Show {
-    question: bool,
-    hint1: bool,
-    hint2: bool,
-    answer: bool,
+/// The file the current quiz was loaded from, polled for changes made in an external editor.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone)]
+struct SourceFile {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    last_poll: Instant,
+    /// Set once the file on disk differs from the loaded quiz, until the user reacts.
+    changed: bool,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl SourceFile {
+    const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+    fn new(path: PathBuf) -> Self {
+        Self {
+            modified: modified_time(&path),
+            path,
+            last_poll: Instant::now(),
+            changed: false,
+        }
+    }
+
+    /// Checks the modification time at most once per [`Self::POLL_INTERVAL`], and only while
+    /// the window has focus. Switching back from the editor is a focus change, which repaints
+    /// and so checks right away.
+    fn poll(&mut self, ctx: &Context) {
+        if !ctx.input(|i| i.focused) {
+            return;
+        }
+        if self.last_poll.elapsed() >= Self::POLL_INTERVAL {
+            self.last_poll = Instant::now();
+            let modified = modified_time(&self.path);
+            if modified != self.modified {
+                self.modified = modified;
+                self.changed = true;
+            }
+        }
+        ctx.request_repaint_after(Self::POLL_INTERVAL);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn modified_time(path: &std::path::Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
 }
 
 /// We derive Deserialize/Serialize so we can persist app state on shutdown.
 #[derive(Deserialize, Serialize)]
 #[serde(default)] // if we add new fields, give them default values when deserializing old state
 pub struct MyApp {
+    /// The [`SCHEMA_VERSION`] the state was saved with. States from before the field existed
+    /// read as 0.
+    #[serde(default)]
+    schema_version: u32,
     pixels_per_point: f32,
-    questions: Option<Vec<Question>>,
-    question_nr: usize,
+    /// Used for selections, links and pressed widgets, e.g. to match a school's colors. `None`
+    /// keeps egui's own colors of the dark and light theme.
+    accent: Option<Color32>,
+    /// Where states before version 3 kept the accent color, see [`MyApp::migrate`].
+    #[serde(rename = "accent_color", skip_serializing)]
+    legacy_accent_color: Color32,
+    /// The questions, the position and what is revealed. Empty while no quiz is loaded.
+    quiz: Quiz,
+    /// Where states before version 2 kept [`Quiz::questions`], see [`MyApp::migrate`].
+    #[serde(rename = "questions", skip_serializing)]
+    legacy_questions: Option<Vec<Question>>,
+    #[serde(rename = "question_nr", skip_serializing)]
+    legacy_question_nr: usize,
+    #[serde(rename = "show", skip_serializing)]
+    legacy_show: Show,
+    #[serde(rename = "tag_filter", skip_serializing)]
+    legacy_tag_filter: TagFilter,
     prev_question_nr: usize,
-    show: Show,
+    /// What is revealed whenever a new question is shown.
+    default_show: Show,
+    reveal_mode: RevealMode,
+    question_align: QuestionAlign,
+    /// Shrink the font of questions too long for the screen, see [`fitted_text`].
+    auto_fit_text: bool,
+    /// Join the lines of multi-line questions and answers into one, see [`collapse_lines`].
+    collapse_line_breaks: bool,
+    /// Hide `<<`, `>>` and the question number, leaving the arrow keys to navigate.
+    hide_navigation: bool,
+    /// Show the question and answer as the two sides of a card that is flipped instead.
+    flashcards: bool,
+    reveal_counts: RevealCounts,
+    last_results: LastResults,
+    /// [`RevealCounts::quiz_key`] of the loaded quiz.
+    #[serde(skip)]
+    quiz_key: u64,
+    /// Whether revealing the current answer was already counted in `reveal_counts`.
+    #[serde(skip)]
+    answer_counted: bool,
+    /// What was revealed of each question when it was left, `None` if it wasn't visited yet.
+    #[serde(skip)]
+    reveals: Vec<Option<Show>>,
+    /// Reveal the question text right away whenever the question changes.
+    auto_show_question: bool,
+    /// Present the hints in a random order on every question.
+    shuffle_hints: bool,
+    /// Order the hints of each question by [`Self::hint_seed`] instead, so the same seed gives
+    /// the same order in every lesson.
+    vary_hints: bool,
+    hint_seed: u32,
+    /// Seconds after arriving at a question before its answer can be revealed.
+    answer_delay: f32,
+    keys: KeyBindings,
+    /// Go to the next question once the time limit of a question ran out.
+    advance_on_time_limit: bool,
+    end_behavior: EndBehavior,
+    /// How many hints and answers may be revealed by hand until "Neu starten", if limited.
+    /// The slideshow and time limits reveal answers for free.
+    reveal_budget: Option<u32>,
+    /// What is left of [`Self::reveal_budget`]. Kept across restarts of the app.
+    reveals_left: u32,
+    /// Set once the time limit of the last question moved on, so it doesn't again every frame.
+    #[serde(skip)]
+    time_limit_ended_quiz: bool,
+    /// Seconds after revealing the answer until the next question, if it should follow on its
+    /// own ("nach Antwort automatisch weiter").
+    advance_after_answer: Option<f32>,
+    /// `egui::InputState::time` since when the answer of the current question is shown, while
+    /// it is.
+    #[serde(skip)]
+    answer_shown_at: Option<f64>,
+    /// Seconds the answer of the current question was shown before `answer_shown_at`, summed
+    /// up over every time it was revealed. See [`Self::answer_seconds`].
+    #[serde(skip)]
+    answer_seconds_before: f64,
+    /// Set once [`Self::advance_after_answer`] moved on from the current question.
+    #[serde(skip)]
+    advanced_after_answer: bool,
+    /// Whether the summary is shown after the last question, see [`EndBehavior::Summary`].
+    #[serde(skip)]
+    summary: bool,
+    /// Which hint is displayed in which slot while `shuffle_hints` or `vary_hints` is on.
+    #[serde(skip)]
+    hint_order: [usize; 2],
+    import_options: ImportOptions,
+    export_options: ExportOptions,
+    playlist: Playlist,
+    slideshow: Option<SlideshowConfig>,
+    /// Seconds elapsed on the current question when the slideshow was paused.
+    #[serde(skip)]
+    slideshow_paused: Option<f64>,
+    /// When the current question was first shown, in [`egui::InputState::time`] seconds.
+    #[serde(skip)]
+    question_arrived: f64,
+    #[serde(skip)]
+    file_io: (Sender<FileMsg>, Receiver<FileMsg>),
+    /// Whether a picked file is being read.
+    #[serde(skip)]
+    loading: bool,
+    #[serde(skip)]
+    playlist_io: (Sender<PlaylistEntry>, Receiver<PlaylistEntry>),
+    #[serde(skip)]
+    images: ImageCache,
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    source: Option<SourceFile>,
+    /// The file the quiz was last loaded from.
+    #[cfg(not(target_arch = "wasm32"))]
+    last_file: Option<PathBuf>,
+    /// Reload [`Self::last_file`] on startup to pick up changes made in the meantime.
+    #[cfg(not(target_arch = "wasm32"))]
+    load_last_file_on_start: bool,
+    /// Counts the saves, to notice when the browser refused to store one.
+    #[cfg(target_arch = "wasm32")]
+    save_generation: u64,
+    /// Set when the browser's storage was full at the last save.
+    #[cfg(target_arch = "wasm32")]
+    #[serde(skip)]
+    storage_full: bool,
+    #[serde(skip)]
+    stashed_quiz: Option<StashedQuiz>,
+    #[serde(skip)]
+    find_replace: Option<FindReplace>,
+    #[serde(skip)]
+    toasts: Toasts,
+    /// The format of the loaded quiz, if its file name didn't say so.
+    #[serde(skip)]
+    detected_format: Option<QuizFormat>,
+    /// The differences to another file, shown in a window until it is closed.
     #[serde(skip)]
-    file_io: (Sender<String>, Receiver<String>),
+    comparison: Option<QuizDiff>,
+    /// The text in the paste window while it is open.
+    #[serde(skip)]
+    pasted_quiz: Option<String>,
+    #[serde(skip)]
+    jump_search: JumpSearch,
+    /// Whether the window with the current question as a QR code is open.
+    #[serde(skip)]
+    qr_code: bool,
+    /// Show all questions and answers at once instead of the current question.
+    #[serde(skip)]
+    answer_sheet: bool,
+    /// Show text fields for the current question instead of the quiz.
+    #[serde(skip)]
+    editing: bool,
+    /// Counts the loads of a quiz or session, so one that finishes after a newer one started is
+    /// ignored, like a file picked in an older "Quiz öffnen" dialog.
+    #[serde(skip)]
+    load_generation: u64,
+    /// Set for one frame to put the keyboard focus on the question field.
+    #[serde(skip)]
+    focus_question_field: bool,
+    /// The answer being corrected in place after a double click on it.
+    #[serde(skip)]
+    answer_edit: Option<String>,
+    /// Whether the quiz was edited or created since it was last loaded or saved.
+    #[serde(skip)]
+    unsaved_changes: bool,
+    /// Take a screenshot in the next frame, once the menu is closed.
+    #[serde(skip)]
+    screenshot_requested: bool,
+    /// Where the question was drawn in the last frame, to crop screenshots to.
+    #[serde(skip)]
+    central_rect: egui::Rect,
+    /// The question image clicked to be viewed fullscreen, see [`ImageCache::lightbox`].
+    #[serde(skip)]
+    lightbox: Option<String>,
+    /// The running "Prüfungsmodus", see [`Exam`].
+    #[serde(skip)]
+    exam: Option<Exam>,
+    /// Set when a frame panicked, to show the error screen instead of the quiz.
+    #[serde(skip)]
+    panic_message: Option<String>,
 }
 
 impl Default for MyApp {
     fn default() -> Self {
         Self {
+            schema_version: SCHEMA_VERSION,
             pixels_per_point: 4.0,
-            questions: None,
-            question_nr: 0,
+            accent: None,
+            legacy_accent_color: DEFAULT_ACCENT_COLOR,
+            quiz: Quiz::default(),
+            legacy_questions: None,
+            legacy_question_nr: 0,
+            legacy_show: Show::default(),
+            legacy_tag_filter: TagFilter::default(),
             prev_question_nr: 0,
-            show: Default::default(),
+            default_show: Default::default(),
+            reveal_mode: Default::default(),
+            question_align: Default::default(),
+            hide_navigation: false,
+            auto_fit_text: false,
+            flashcards: false,
+            collapse_line_breaks: false,
+            reveal_counts: Default::default(),
+            last_results: Default::default(),
+            quiz_key: 0,
+            answer_counted: false,
+            reveals: Vec::new(),
+            auto_show_question: false,
+            shuffle_hints: false,
+            vary_hints: false,
+            hint_seed: 1,
+            answer_delay: 0.0,
+            keys: Default::default(),
+            advance_on_time_limit: false,
+            end_behavior: Default::default(),
+            advance_after_answer: None,
+            answer_shown_at: None,
+            answer_seconds_before: 0.0,
+            advanced_after_answer: false,
+            reveal_budget: None,
+            reveals_left: 0,
+            time_limit_ended_quiz: false,
+            summary: false,
+            hint_order: [0, 1],
+            import_options: Default::default(),
+            export_options: Default::default(),
+            playlist: Default::default(),
+            slideshow: None,
+            slideshow_paused: None,
+            question_arrived: 0.0,
             file_io: channel(),
+            loading: false,
+            playlist_io: channel(),
+            images: Default::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            source: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            last_file: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            load_last_file_on_start: false,
+            #[cfg(target_arch = "wasm32")]
+            save_generation: 0,
+            #[cfg(target_arch = "wasm32")]
+            storage_full: false,
+            stashed_quiz: None,
+            find_replace: None,
+            pasted_quiz: None,
+            comparison: None,
+            detected_format: None,
+            toasts: Default::default(),
+            jump_search: Default::default(),
+            qr_code: false,
+            answer_sheet: false,
+            editing: false,
+            load_generation: 0,
+            focus_question_field: false,
+            answer_edit: None,
+            unsaved_changes: false,
+            screenshot_requested: false,
+            central_rect: egui::Rect::NOTHING,
+            lightbox: None,
+            exam: None,
+            panic_message: None,
         }
     }
 }
@@ -49,129 +479,1995 @@ impl MyApp {
     pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
         // This is also where you can customize the look and feel of egui using
         // `cc.egui_ctx.set_visuals` and `cc.egui_ctx.set_fonts`.
+        egui_extras::install_image_loaders(&cc.egui_ctx);
+        crate::panic::install_hook();
 
         // Load previous app state (if any).
         // Note that you must enable the `persistence` feature for this to work.
         if let Some(storage) = cc.storage {
-            let mut app: Self = eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
-            app.show = Default::default();
+            let stored = storage.get_string(eframe::APP_KEY).is_some();
+            let loaded: Option<Self> = eframe::get_value(storage, eframe::APP_KEY);
+            let mut warning = match (stored, &loaded) {
+                (true, None) => Some("konnte nicht gelesen werden".to_owned()),
+                _ => None,
+            };
+            let mut app = match loaded.map(Self::migrate) {
+                Some(Ok(app)) => app,
+                Some(Err(err)) => {
+                    warning = Some(err);
+                    Self::default()
+                }
+                None => Self::default(),
+            };
+            if let Some(warning) = warning {
+                log::warn!("Discarding the saved state: {warning}");
+                app.toasts.error(
+                    &cc.egui_ctx,
+                    format!("Gespeicherter Zustand {warning}, es wird neu begonnen"),
+                );
+            }
+            app.playlist.retain_readable();
+            app.clamp_question_nr();
+            app.reset_reveals(&cc.egui_ctx);
+            apply_visuals(&cc.egui_ctx, app.accent);
+            // `new` runs before the event loop, so this is picked up on the first frame.
+            #[cfg(not(target_arch = "wasm32"))]
+            if let (true, Some(path)) = (app.load_last_file_on_start, app.last_file.clone()) {
+                let msg = read_file_msg(&path, app.playlist.current, app.start_load());
+                let _ = app.file_io.0.send(msg);
+            }
+            // On the very first start there is something to try right away.
+            if !stored {
+                let generation = app.start_load();
+                load_example_quiz(&app.file_io.0, &cc.egui_ctx, generation);
+            }
             return app;
         }
-        Default::default()
+        let mut app = Self::default();
+        let generation = app.start_load();
+        load_example_quiz(&app.file_io.0, &cc.egui_ctx, generation);
+        app
     }
 }
 
 impl eframe::App for MyApp {
     /// Called by the frame work to save state before shutdown.
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.save_generation += 1;
+        }
         eframe::set_value(storage, eframe::APP_KEY, self);
+        // The browser drops writes beyond its storage quota without telling eframe, so check
+        // whether this save actually arrived.
+        #[cfg(target_arch = "wasm32")]
+        {
+            #[derive(Deserialize)]
+            struct Saved {
+                save_generation: u64,
+            }
+            let saved: Option<Saved> = eframe::get_value(storage, eframe::APP_KEY);
+            self.storage_full = saved.map_or(true, |s| s.save_generation != self.save_generation);
+        }
     }
 
-    /// Called each time the UI needs repainting, which may be many times per second.
+    /// Called each time the UI needs repainting. egui only repaints on input unless a repaint
+    /// is requested, so everything that changes on its own (slideshow, time limit, answer delay,
+    /// toasts, file watching, downloads) requests one for when it next changes. The ring of a
+    /// time limit moves ten times a second. The time an answer has been visible ticks once a
+    /// second, as does the check of a loaded file for changes while the window has focus. An
+    /// idle app with none of these doesn't redraw at all.
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        if self.panic_message.is_some() {
+            self.panic_screen(ctx);
+            return;
+        }
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| self.ui(ctx)));
+        if result.is_err() {
+            let message = crate::panic::take_message().unwrap_or_default();
+            self.panic_message = Some(message);
+            ctx.request_repaint();
+        }
+    }
+}
+
+impl MyApp {
+    fn ui(&mut self, ctx: &egui::Context) {
         ctx.set_pixels_per_point(self.pixels_per_point);
-        if self.question_nr != self.prev_question_nr {
-            self.prev_question_nr = self.question_nr;
-            self.show = Default::default();
+        self.count_reveal();
+        self.keyboard_shortcuts(ctx);
+        let now = ctx.input(|i| i.time);
+        self.track_answer_time(now);
+        if self.is_editing() {
+            // The clocks of the current question hold while it is edited. The editor shows the
+            // answer, so that time still counts towards `answer_seconds`.
+            self.question_arrived = now;
+            if let Some(shown_at) = &mut self.answer_shown_at {
+                self.answer_seconds_before += now - *shown_at;
+                *shown_at = now;
+            }
+        }
+        self.run_slideshow(ctx);
+        self.run_time_limit(ctx);
+        self.run_advance_after_answer(ctx);
+        if self.quiz.question_nr != self.prev_question_nr {
+            if let Some(reveal) = self.reveals.get_mut(self.prev_question_nr) {
+                *reveal = Some(self.quiz.show);
+            }
+            self.prev_question_nr = self.quiz.question_nr;
+            self.enter_question(ctx);
         }
 
         // Parsing questions from file picker
-        if let Ok(quiz) = self.file_io.1.try_recv() {
-            if let Ok(quiz) = serde_json::from_str::<Vec<Question>>(&quiz) {
-                if quiz.len() >= 1 {
-                    self.questions = Some(quiz);
-                    self.question_nr = 0;
-                }
-            }
+        self.receive_files(ctx);
+        self.receive_screenshot(ctx);
+        while let Ok(entry) = self.playlist_io.1.try_recv() {
+            self.playlist.entries.push(entry);
         }
 
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             egui::menu::bar(ui, |ui| {
-                if ui.button("+").highlight().clicked() {
-                    self.pixels_per_point += 0.1;
+                ui.menu_button("Datei", |ui| self.file_menu(ui));
+                ui.menu_button("Ansicht", |ui| self.view_menu(ui));
+                ui.menu_button("Quiz", |ui| self.quiz_menu(ui));
+                if self.loading {
+                    ui.spinner();
                 }
-                if ui.button("−").highlight().clicked() {
-                    self.pixels_per_point = (self.pixels_per_point - 0.1).max(0.1);
+                if let Some(format) = self.detected_format {
+                    ui.weak(format!("als {} erkannt", format.name()));
                 }
-
-                ui.separator();
-                if ui.button("Quiz öffnen").clicked() {
-                    let ctx = ctx.clone();
-                    let tx = self.file_io.0.clone();
-                    file_dialog(tx, ctx); // opens the file dialog in a background thread
+                if self.is_editing() {
+                    ui.strong("Bearbeiten");
+                }
+                if self.unsaved_changes {
+                    ui.weak("ungespeichert");
+                }
+                if self.reveal_budget.is_some() {
+                    ui.separator();
+                    ui.label(format!("Aufdecken: noch {}", self.reveals_left));
+                }
+                if let Some(questions) = loaded(&self.quiz.questions) {
+                    if self.exam.is_none() {
+                        ui.separator();
+                        if let Some(nr) = self.jump_search.ui(ui, questions) {
+                            self.quiz.question_nr = nr;
+                        }
+                    }
+                    ui.separator();
+                    self.progress_dots(ui);
                 }
             });
         });
 
-        egui::CentralPanel::default().show(ctx, |ui| {
-            let question = self.questions.as_ref().map(|q| q.get(self.question_nr));
-            if let Some(Some(question)) = question {
+        #[cfg(not(target_arch = "wasm32"))]
+        self.source_file_prompt(ctx);
+        #[cfg(target_arch = "wasm32")]
+        self.storage_full_banner(ctx);
+        self.find_replace_window(ctx);
+        self.qr_code_window(ctx);
+        self.paste_window(ctx);
+        self.comparison_window(ctx);
+        self.toasts.show(ctx);
+
+        let central = egui::CentralPanel::default().show(ctx, |ui| {
+            if self.is_editing() {
+                self.edit_ui(ui);
+                return;
+            }
+            let Some(questions) = loaded(&self.quiz.questions) else {
+                let (open, example) = empty_state_ui(ui);
+                if open {
+                    self.open_quiz(ctx);
+                }
+                if example {
+                    let generation = self.start_load();
+                    load_example_quiz(&self.file_io.0, ctx, generation);
+                }
+                return;
+            };
+            if let Some(exam) = self.exam.as_ref().filter(|exam| exam.finished) {
+                if exam.results_ui(ui, questions) {
+                    self.exam = None;
+                }
+                return;
+            }
+            if self.answer_sheet && self.exam.is_none() {
+                answer_sheet_ui(ui, questions, &mut self.images);
+                return;
+            }
+            if self.summary {
+                ui.heading("Ende des Quiz");
+                QuizStats::new(questions).ui(ui);
+                let first = self.quiz.first().unwrap_or(0);
                 ui.horizontal(|ui| {
-                    ui.label("Frage: ");
-                    if ui.button("<<").clicked() {
-                        self.question_nr = self.question_nr.saturating_sub(1);
+                    if ui.button("Zurück zur letzten Frage").clicked() {
+                        self.summary = false;
                     }
-                    if let Some(questions) = self.questions.as_ref() {
+                    if ui.button("Von vorne beginnen").clicked() {
+                        self.summary = false;
+                        self.quiz.question_nr = first;
+                    }
+                });
+                return;
+            }
+            let mut toggle_pause = false;
+            if self.slideshow.is_some() {
+                let id = ui.id().with("slideshow_pause");
+                toggle_pause |= ui
+                    .interact(ui.max_rect(), id, egui::Sense::click())
+                    .clicked();
+            }
+            let mut reached_end = false;
+            let mut edited_answer = None;
+            let can_reveal = self.can_reveal();
+            // Hints and answers revealed by hand in this frame, each costs one of the budget.
+            let mut revealed = 0;
+            let mut handed_in = false;
+            if let Some(question) = questions.get(self.quiz.question_nr) {
+                let (prev, next) = (self.quiz.neighbor(false), self.quiz.neighbor(true));
+                ui.horizontal(|ui| {
+                    if !self.hide_navigation {
+                        ui.label("Frage: ");
+                        if let (true, Some(prev)) = (ui.button("<<").clicked(), prev) {
+                            self.quiz.question_nr = prev;
+                        }
                         ui.add(
-                            egui::widgets::DragValue::new(&mut self.question_nr)
-                                .range(0..=questions.len()),
+                            egui::widgets::DragValue::new(&mut self.quiz.question_nr)
+                                .range(0..=questions.len().saturating_sub(1)),
                         );
+                        if ui.button(">>").clicked() {
+                            match next {
+                                Some(next) => self.quiz.question_nr = next,
+                                None => reached_end = true,
+                            }
+                        }
+                    }
+                    if let Some(position) = self.playlist.position_label() {
+                        ui.label(position);
+                    }
+                    if self.slideshow.is_some() {
+                        let icon = match self.slideshow_paused {
+                            Some(_) => "▶",
+                            None => "⏸",
+                        };
+                        toggle_pause |= ui.button(icon).clicked();
                     }
-                    if ui.button(">>").clicked() {
-                        self.question_nr = self.question_nr.saturating_add(1);
+                    if let Some(limit) = question.time_limit {
+                        let elapsed = ui.input(|i| i.time) - self.question_arrived;
+                        time_limit_ring(ui, elapsed, limit);
+                    }
+                    if self.last_results.has_quiz(self.quiz_key) {
+                        let (icon, color) = match self.last_results.get(self.quiz_key, question) {
+                            Some(true) => ("✔", Color32::GREEN),
+                            Some(false) => ("✖", ui.visuals().error_fg_color),
+                            None => ("—", ui.visuals().weak_text_color()),
+                        };
+                        ui.colored_label(color, icon)
+                            .on_hover_text("Ergebnis in der letzten Prüfung");
+                    }
+                    if self.exam.is_none() {
+                        ui.toggle_value(&mut self.qr_code, "QR")
+                            .on_hover_text("Frage als QR-Code zeigen");
                     }
                 });
 
-                if ui.button("Frage: ").clicked() {
-                    self.show.question ^= true;
+                if let (true, None) = (self.flashcards, &self.exam) {
+                    let can_flip = self.quiz.show.answer
+                        || (can_reveal && self.answer_locked_for(ui.input(|i| i.time)) == 0.0);
+                    let answers = question.answers_text();
+                    let card = flashcard_ui(
+                        ui,
+                        (self.quiz_key, self.quiz.question_nr),
+                        &question.question,
+                        &answers,
+                        self.quiz.show.answer,
+                    );
+                    if card.clicked() && can_flip {
+                        self.quiz.show.answer = !self.quiz.show.answer;
+                        revealed += u32::from(self.quiz.show.answer);
+                    }
+                    let hints = self.quiz.show.hint1;
+                    if ui.small_button("💡").on_hover_text("Hinweise").clicked()
+                        && (hints || can_reveal)
+                    {
+                        // The second hint only comes along if the budget has room for it.
+                        let both = self.reveal_budget.is_none() || self.reveals_left >= 2;
+                        self.quiz.show.hint1 = !hints;
+                        self.quiz.show.hint2 = !hints && both;
+                        if !hints {
+                            revealed += 1 + u32::from(both);
+                        }
+                    }
+                    if self.quiz.show.hint1 {
+                        self.images.show_text_or_image(ui, &question.hint1);
+                    }
+                    if self.quiz.show.hint2 {
+                        self.images.show_text_or_image(ui, &question.hint2);
+                    }
+                } else {
+                    tag_breadcrumbs(ui, &question.tags, &mut self.quiz.tag_filter);
+                    ui.toggle_value(&mut self.quiz.show.question, "Frage: ");
+                    let auto_fit_text = self.auto_fit_text;
+                    let collapse = self.collapse_line_breaks;
+                    ui.with_layout(self.question_align.layout(), |ui| {
+                        let text = collapse_lines(&question.question, collapse);
+                        match (self.quiz.show.question, auto_fit_text) {
+                            (true, true) => ui.add(egui::Label::new(fitted_text(ui, &text)).wrap()),
+                            (true, false) => ui.add(egui::Label::new(text.as_ref()).wrap()),
+                            (false, _) => ui.label(""),
+                        };
+                    });
+                    if let (true, Some(image)) = (self.quiz.show.question, &question.image) {
+                        let response = self.images.show(ui, image);
+                        if response.on_hover_cursor(egui::CursorIcon::ZoomIn).clicked() {
+                            self.lightbox = Some(image.clone());
+                        }
+                    }
+                    tag_chips(ui, &question.tags);
+
+                    if let Some(exam) = &mut self.exam {
+                        handed_in = exam.response_ui(ui, self.quiz.question_nr);
+                    } else {
+                        let hints = [&question.hint1, &question.hint2];
+                        let order = match self.shuffle_hints || self.vary_hints {
+                            true => self.hint_order,
+                            false => [0, 1],
+                        };
+                        let shown = [&mut self.quiz.show.hint1, &mut self.quiz.show.hint2];
+                        for (slot, shown) in shown.into_iter().enumerate() {
+                            // The header follows `Show` so that navigating resets it like the rest.
+                            let header = ui.add_enabled_ui(*shown || can_reveal, |ui| {
+                                egui::CollapsingHeader::new(format!("Hinweis {}", slot + 1))
+                                    .id_salt(("hint", slot))
+                                    .open(Some(*shown))
+                                    .show(ui, |ui| {
+                                        self.images.show_text_or_image(ui, hints[order[slot]])
+                                    })
+                            });
+                            if header.inner.header_response.clicked() {
+                                *shown = !*shown;
+                                revealed += u32::from(*shown);
+                            }
+                        }
+
+                        let locked_for = self.answer_locked_for(ui.input(|i| i.time));
+                        if locked_for > 0.0 {
+                            // Only the whole seconds are shown, so wake up when the next one starts.
+                            let until_next_second = locked_for - (locked_for.ceil() - 1.0);
+                            ui.ctx().request_repaint_after_secs(until_next_second);
+                        }
+                        let seconds = self.answer_seconds(ui.input(|i| i.time));
+                        let answer_running = self.answer_shown_at.is_some();
+                        ui.horizontal(|ui| {
+                            let label = match locked_for > 0.0 {
+                                true => format!("Antwort ({:.0} s): ", locked_for.ceil()),
+                                false => "Antwort: ".to_owned(),
+                            };
+                            let enabled =
+                                locked_for == 0.0 && (self.quiz.show.answer || can_reveal);
+                            ui.add_enabled_ui(enabled, |ui| {
+                                let toggled =
+                                    ui.toggle_value(&mut self.quiz.show.answer, label).clicked();
+                                revealed += u32::from(toggled && self.quiz.show.answer);
+                            });
+                            let more_letters = egui::Button::new("Buchstabe zeigen");
+                            let enabled = !self.quiz.show.answer && locked_for == 0.0 && can_reveal;
+                            if ui.add_enabled(enabled, more_letters).clicked() {
+                                revealed += 1;
+                                self.quiz.show.reveal_letter(&question.answer);
+                            }
+                            if seconds >= 1.0 {
+                                ui.weak(format!("⏱ {seconds:.0} s")).on_hover_text(
+                                    "So lange war die Antwort bei dieser Frage insgesamt zu sehen",
+                                );
+                            }
+                            if answer_running {
+                                ui.ctx()
+                                    .request_repaint_after_secs(1.0 - seconds.fract() as f32);
+                            }
+                        });
+                        // Peeking would get around the budget and the answer delay, so it is
+                        // off while there is a budget or the answer is still locked.
+                        let peeking = self.keys.peek_answer.is_some_and(|key| {
+                            self.reveal_budget.is_none()
+                                && locked_for <= 0.0
+                                && !ui.ctx().wants_keyboard_input()
+                                && ui.input(|i| i.key_down(key))
+                        });
+                        match (
+                            self.quiz.show.answer || peeking,
+                            self.quiz.show.answer_letters,
+                        ) {
+                            (true, _) => match &mut self.answer_edit {
+                                Some(text) => {
+                                    let response = ui.text_edit_singleline(text);
+                                    if !response.has_focus() && !response.lost_focus() {
+                                        response.request_focus();
+                                    }
+                                    // Enter keeps the new answer, clicking elsewhere or Escape drops it.
+                                    if response.lost_focus() {
+                                        let text = self.answer_edit.take();
+                                        if ui.input(|i| i.key_pressed(Key::Enter)) {
+                                            edited_answer = text;
+                                        }
+                                    }
+                                    response
+                                }
+                                None => {
+                                    let answers = question.answers_text();
+                                    let text = collapse_lines(&answers, collapse);
+                                    let response = ui
+                                        .add(
+                                            egui::Label::new(text)
+                                                .wrap()
+                                                .sense(egui::Sense::click()),
+                                        )
+                                        .on_hover_text("Doppelklick zum Bearbeiten");
+                                    if response.double_clicked() {
+                                        self.answer_edit = Some(question.answer.clone());
+                                    }
+                                    response
+                                }
+                            },
+                            (false, 0) => ui.label(""),
+                            (false, letters) => ui.label(
+                                RichText::new(answer_mask(&question.answer, letters)).monospace(),
+                            ),
+                        };
+
+                        let reveal_count = self.reveal_counts.get(self.quiz_key, question);
+                        if reveal_count > 0 {
+                            ui.label(
+                                RichText::new(format!("{reveal_count}× aufgedeckt"))
+                                    .small()
+                                    .weak(),
+                            );
+                        }
+                    }
+
+                    if let Some(source) = &question.source {
+                        ui.add_space(8.0);
+                        ui.label(RichText::new(format!("Quelle: {source}")).small().weak());
+                    }
                 }
-                match self.show.question {
-                    true => ui.label(RichText::new(&question.question)),
-                    false => ui.label(""),
-                };
+            };
+            self.spend_reveals(revealed);
+            if handed_in {
+                self.finish_exam();
+            }
+            if let Some(answer) = edited_answer {
+                if let Some(question) = self.quiz.questions.get_mut(self.quiz.question_nr) {
+                    question.answer = answer;
+                    self.unsaved_changes = true;
+                }
+            }
+            if reached_end {
+                self.reach_end();
+            }
+            if toggle_pause {
+                self.toggle_slideshow_pause(ctx);
+            }
+        });
+        self.central_rect = central.response.rect;
+        if let Some(image) = &self.lightbox {
+            if self.images.lightbox(ctx, image) {
+                self.lightbox = None;
+            }
+        }
+    }
+}
 
-                if ui.button("Hinweis 1: ").clicked() {
-                    self.show.hint1 ^= true;
+impl MyApp {
+    /// Upgrades a state saved by an older version step by step to [`SCHEMA_VERSION`].
+    fn migrate(mut self) -> Result<Self, String> {
+        while self.schema_version < SCHEMA_VERSION {
+            match self.schema_version {
+                // Everything added before the version field is filled in by `serde(default)`.
+                0 => {}
+                1 => {
+                    self.quiz = Quiz {
+                        questions: self.legacy_questions.take().unwrap_or_default(),
+                        question_nr: self.legacy_question_nr,
+                        show: self.legacy_show,
+                        tag_filter: std::mem::take(&mut self.legacy_tag_filter),
+                        results: Vec::new(),
+                    };
                 }
-                match self.show.hint1 {
-                    true => ui.label(&question.hint1),
-                    false => ui.label(""),
-                };
+                // The default accent was always applied, so only a picked one is kept.
+                2 => {
+                    let picked = self.legacy_accent_color != DEFAULT_ACCENT_COLOR;
+                    self.accent = picked.then_some(self.legacy_accent_color);
+                }
+                version => return Err(format!("hat die unbekannte Version {version}")),
+            }
+            self.schema_version += 1;
+        }
+        // A newer version's fields are dropped, but what this version knows is kept.
+        if self.schema_version > SCHEMA_VERSION {
+            log::warn!(
+                "The saved state has version {}, newer than {SCHEMA_VERSION}",
+                self.schema_version
+            );
+            self.schema_version = SCHEMA_VERSION;
+        }
+        Ok(self)
+    }
 
-                if ui.button("Hinweis 2: ").clicked() {
-                    self.show.hint2 ^= true;
+    /// Whether the editor is shown instead of the quiz. It is never shown during an exam,
+    /// because it would give the answers away.
+    fn is_editing(&self) -> bool {
+        self.editing && !self.quiz.questions.is_empty() && self.exam.is_none()
+    }
+
+    /// Opens the file dialog in a background thread.
+    fn open_quiz(&mut self, ctx: &Context) {
+        let generation = self.start_load();
+        file_dialog(self.file_io.0.clone(), ctx.clone(), generation);
+    }
+
+    /// Supersedes every load still in flight, see [`Self::load_generation`].
+    fn start_load(&mut self) -> u64 {
+        self.load_generation += 1;
+        self.load_generation
+    }
+
+    fn keyboard_shortcuts(&mut self, ctx: &Context) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        if ctx.input_mut(|i| i.consume_shortcut(&OPEN_SHORTCUT)) {
+            self.open_quiz(ctx);
+        }
+        // Like the "Bearbeiten" checkbox, which is off during an exam.
+        let can_edit = !self.quiz.questions.is_empty() && self.exam.is_none();
+        if can_edit && ctx.input_mut(|i| i.consume_shortcut(&EDIT_SHORTCUT)) {
+            self.editing = !self.editing;
+        }
+        // In the slideshow, the space bar pauses instead. The exam has no answer to show.
+        let flip_key = match (self.flashcards, self.slideshow) {
+            (true, None) => Some(Key::Space),
+            _ => None,
+        };
+        let answer_keys = match self.exam {
+            Some(_) => [None, None],
+            None => [self.keys.toggle_answer, flip_key],
+        };
+        for key in answer_keys.into_iter().flatten() {
+            let now = ctx.input(|i| i.time);
+            if self.answer_locked_for(now) == 0.0
+                && (self.quiz.show.answer || self.can_reveal())
+                && ctx.input_mut(|i| i.consume_key(Modifiers::NONE, key))
+            {
+                self.quiz.show.answer = !self.quiz.show.answer;
+                if self.quiz.show.answer {
+                    self.spend_reveals(1);
                 }
-                match self.show.hint2 {
-                    true => ui.label(&question.hint2),
-                    false => ui.label(""),
-                };
+            }
+        }
+        for (key, forward) in [(Key::ArrowLeft, false), (Key::ArrowRight, true)] {
+            if ctx.input_mut(|i| i.consume_key(Modifiers::NONE, key)) {
+                self.step_question(forward);
+            }
+        }
+    }
+
+    /// Counts the answer of the current question as revealed, once per visit. Answers the
+    /// slideshow reveals on its own don't count.
+    fn count_reveal(&mut self) {
+        if !self.quiz.show.answer || self.answer_counted {
+            return;
+        }
+        self.answer_counted = true;
+        if let (None, Some(question)) = (self.slideshow, self.quiz.current()) {
+            self.reveal_counts.record(self.quiz_key, question);
+        }
+    }
+
+    /// Whether the budget allows another hint or answer, see [`Self::reveal_budget`].
+    fn can_reveal(&self) -> bool {
+        self.reveal_budget.is_none() || self.reveals_left > 0
+    }
+
+    fn spend_reveals(&mut self, count: u32) {
+        if self.reveal_budget.is_some() {
+            self.reveals_left = self.reveals_left.saturating_sub(count);
+        }
+    }
+
+    /// Starts or stops the clock for [`Self::answer_seconds`] when the answer is revealed or
+    /// hidden.
+    fn track_answer_time(&mut self, now: f64) {
+        match (self.quiz.show.answer, self.answer_shown_at) {
+            (true, None) => self.answer_shown_at = Some(now),
+            (false, Some(shown_at)) => {
+                self.answer_seconds_before += now - shown_at;
+                self.answer_shown_at = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// How long the answer of the current question was shown in total since navigating to it.
+    fn answer_seconds(&self, now: f64) -> f64 {
+        let running = self.answer_shown_at.map_or(0.0, |shown_at| now - shown_at);
+        self.answer_seconds_before + running
+    }
 
-                if ui.button("Antwort: ").clicked() {
-                    self.show.answer ^= true;
+    /// Seconds until the answer can be revealed, see [`Self::answer_delay`].
+    fn answer_locked_for(&self, now: f64) -> f32 {
+        let waited = (now - self.question_arrived) as f32;
+        match self.quiz.show.answer {
+            true => 0.0,
+            false => (self.answer_delay - waited).max(0.0),
+        }
+    }
+
+    /// Like the `<<` and `>>` buttons: moves to the previous or next question passing the tag
+    /// filter, or on to the next playlist entry after the last one.
+    fn step_question(&mut self, forward: bool) {
+        if self.quiz.questions.is_empty() {
+            return;
+        }
+        match (self.quiz.neighbor(forward), forward) {
+            (Some(nr), _) => self.quiz.question_nr = nr,
+            (None, true) => self.reach_end(),
+            (None, false) => {}
+        }
+    }
+
+    /// Shows the results of the exam, grades them into [`Quiz::results`] and remembers them in
+    /// [`Self::last_results`]. Questions left blank keep their earlier result.
+    fn finish_exam(&mut self) {
+        let Some(exam) = &mut self.exam else {
+            return;
+        };
+        exam.finished = true;
+        for (nr, response) in exam.responses.iter().enumerate() {
+            if response.trim().is_empty() {
+                continue;
+            }
+            let correct = self.quiz.grade(nr, response);
+            if let Some(question) = self.quiz.questions.get(nr) {
+                self.last_results.record(self.quiz_key, question, correct);
+            }
+        }
+    }
+
+    /// Called when moving on from the last question.
+    fn reach_end(&mut self) {
+        if self.exam.is_some() {
+            self.finish_exam();
+            return;
+        }
+        if let Some(entry) = self.playlist.next() {
+            self.play_playlist_entry(entry);
+            return;
+        }
+        match self.end_behavior {
+            EndBehavior::Stop => {}
+            EndBehavior::Loop => self.quiz.question_nr = self.quiz.first().unwrap_or(0),
+            EndBehavior::Summary => self.summary = true,
+        }
+    }
+
+    fn file_menu(&mut self, ui: &mut egui::Ui) {
+        if ui.button("Neues Quiz").clicked() {
+            self.new_quiz(ui.ctx());
+            ui.close_menu();
+        }
+        let open = egui::Button::new("Quiz öffnen")
+            .shortcut_text(ui.ctx().format_shortcut(&OPEN_SHORTCUT));
+        if ui.add(open).clicked() {
+            self.open_quiz(ui.ctx());
+            ui.close_menu();
+        }
+        if ui.button("Aus Zwischenablage einfügen…").clicked() {
+            self.pasted_quiz.get_or_insert_with(String::new);
+            ui.close_menu();
+        }
+        if ui.button("Beispielquiz laden").clicked() {
+            let generation = self.start_load();
+            load_example_quiz(&self.file_io.0, ui.ctx(), generation);
+            ui.close_menu();
+        }
+        ui.menu_button("Vorlage öffnen", |ui| {
+            for (label, name, text) in TEMPLATES {
+                if ui.button(label).clicked() {
+                    let generation = self.start_load();
+                    load_embedded_quiz(&self.file_io.0, ui.ctx(), generation, name, text);
+                    // The template is there to be adapted.
+                    self.editing = true;
+                    ui.close_menu();
+                }
+            }
+        });
+        let save = egui::Button::new("Quiz speichern…");
+        if ui
+            .add_enabled(!self.quiz.questions.is_empty(), save)
+            .clicked()
+        {
+            self.save_quiz(ui.ctx());
+            ui.close_menu();
+        }
+        let undo = egui::Button::new("Rückgängig");
+        if ui.add_enabled(self.stashed_quiz.is_some(), undo).clicked() {
+            self.undo_load(ui.ctx());
+            ui.close_menu();
+        }
+        ui.menu_button("Wiedergabeliste", |ui| match self.playlist.ui(ui) {
+            Some(PlaylistAction::AddFiles) => {
+                playlist_dialog(self.playlist_io.0.clone(), ui.ctx().clone());
+            }
+            Some(PlaylistAction::Play(i)) => self.play_playlist_entry(i),
+            None => {}
+        });
+        ui.separator();
+        #[cfg(not(target_arch = "wasm32"))]
+        ui.checkbox(
+            &mut self.load_last_file_on_start,
+            "Letzte Datei beim Start laden",
+        );
+        ui.menu_button("Import", |ui| self.import_options.ui(ui));
+        ui.menu_button("Export", |ui| {
+            self.export_options.ui(ui);
+            let export = egui::Button::new("Als Anki-Text exportieren…");
+            if ui
+                .add_enabled(!self.quiz.questions.is_empty(), export)
+                .clicked()
+            {
+                if let Some(questions) = loaded(&self.quiz.questions) {
+                    let text = anki_text(questions, &self.export_options);
+                    let tx = self.file_io.0.clone();
+                    save_dialog(
+                        tx,
+                        ui.ctx().clone(),
+                        "quiz.txt",
+                        text.into_bytes(),
+                        FileMsg::Saved,
+                    );
+                }
+                ui.close_menu();
+            }
+            let selection = egui::Button::new("Aktuelle Auswahl exportieren…");
+            if ui
+                .add_enabled(!self.quiz.questions.is_empty(), selection)
+                .on_hover_text("Die Fragen, die der Filter durchlässt, als JSON. Ohne Filter alle.")
+                .clicked()
+            {
+                if let Some(questions) = loaded(&self.quiz.questions) {
+                    let selected: Vec<&Question> = questions
+                        .iter()
+                        .filter(|q| self.quiz.tag_filter.matches(&q.tags))
+                        .collect();
+                    let json =
+                        serde_json::to_vec_pretty(&selected).expect("questions can be serialized");
+                    let tx = self.file_io.0.clone();
+                    save_dialog(tx, ui.ctx().clone(), "auswahl.json", json, FileMsg::Saved);
+                }
+                ui.close_menu();
+            }
+        });
+        let export_session = egui::Button::new("Sitzung exportieren…");
+        if ui
+            .add_enabled(!self.quiz.questions.is_empty(), export_session)
+            .on_hover_text("Quiz, aktuelle Frage und Aufdeckzustand, um woanders weiterzumachen")
+            .clicked()
+        {
+            if let Some(snapshot) = self.session_snapshot() {
+                let tx = self.file_io.0.clone();
+                save_dialog(
+                    tx,
+                    ui.ctx().clone(),
+                    "sitzung.json",
+                    snapshot.to_json(),
+                    FileMsg::Saved,
+                );
+            }
+            ui.close_menu();
+        }
+        if ui.button("Sitzung importieren…").clicked() {
+            let generation = self.start_load();
+            session_dialog(self.file_io.0.clone(), ui.ctx().clone(), generation);
+            ui.close_menu();
+        }
+        if ui.button("Bildcache leeren").clicked() {
+            self.images.clear(ui.ctx());
+            ui.close_menu();
+        }
+    }
+
+    fn view_menu(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Zoom:");
+            if ui.button("−").highlight().clicked() {
+                self.pixels_per_point = (self.pixels_per_point - 0.1).max(0.1);
+            }
+            if ui.button("+").highlight().clicked() {
+                self.pixels_per_point += 0.1;
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Akzentfarbe:");
+            let mut color = self.accent.unwrap_or(DEFAULT_ACCENT_COLOR);
+            let mut changed = ui.color_edit_button_srgba(&mut color).changed();
+            if changed {
+                self.accent = Some(color);
+            }
+            let reset = egui::Button::new("Zurücksetzen");
+            if ui.add_enabled(self.accent.is_some(), reset).clicked() {
+                self.accent = None;
+                changed = true;
+            }
+            if changed {
+                apply_visuals(ui.ctx(), self.accent);
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Fragetext:");
+            ui.selectable_value(&mut self.question_align, QuestionAlign::Left, "links");
+            ui.selectable_value(&mut self.question_align, QuestionAlign::Center, "zentriert");
+            ui.selectable_value(&mut self.question_align, QuestionAlign::Right, "rechts");
+        });
+        ui.checkbox(&mut self.auto_fit_text, "Lange Fragen verkleinern")
+            .on_hover_text(
+                "Die Schrift wird kleiner, bis die Frage auf den halben Bildschirm passt",
+            );
+        ui.checkbox(
+            &mut self.collapse_line_breaks,
+            "Zeilenumbrüche zusammenfassen",
+        )
+        .on_hover_text("Mehrzeilige Fragen und Antworten in einer Zeile zeigen");
+        ui.separator();
+        ui.checkbox(&mut self.hide_navigation, "Navigationsleiste verbergen")
+            .on_hover_text("Mit den Pfeiltasten ← und → blättern");
+        ui.add_enabled_ui(self.exam.is_none(), |ui| {
+            ui.checkbox(&mut self.answer_sheet, "Lösungsbogen");
+            ui.checkbox(&mut self.flashcards, "Karteikarten")
+                .on_hover_text("Leertaste oder Klick dreht die Karte um");
+        });
+        let screenshot = egui::Button::new("Screenshot speichern…");
+        if ui
+            .add_enabled(!self.quiz.questions.is_empty(), screenshot)
+            .on_hover_text("Die aktuelle Frage als PNG-Bild, z. B. zum Teilen")
+            .clicked()
+        {
+            self.screenshot_requested = true;
+            ui.ctx().request_repaint();
+            ui.close_menu();
+        }
+        let mut slideshow = self.slideshow.is_some();
+        if ui.checkbox(&mut slideshow, "Diashow").changed() {
+            self.slideshow = slideshow.then(SlideshowConfig::default);
+            self.slideshow_paused = None;
+            self.question_arrived = ui.input(|i| i.time);
+        }
+        if let Some(slideshow) = self.slideshow.as_mut() {
+            slideshow.ui(ui);
+        }
+    }
+
+    fn quiz_menu(&mut self, ui: &mut egui::Ui) {
+        if let Some(questions) = loaded(&self.quiz.questions) {
+            ui.menu_button("Filter", |ui| {
+                let tags: BTreeSet<&str> = questions
+                    .iter()
+                    .flat_map(|q| q.tags.iter().flat_map(|tag| tag_levels(tag)))
+                    .collect();
+                self.quiz.tag_filter.ui(ui, &tags);
+            });
+            ui.menu_button("Umfang", |ui| QuizStats::new(questions).ui(ui));
+            ui.separator();
+        }
+        ui.radio_value(
+            &mut self.reveal_mode,
+            RevealMode::Reset,
+            "Aufdeckzustand auf jeder Frage zurücksetzen",
+        );
+        ui.radio_value(
+            &mut self.reveal_mode,
+            RevealMode::Remember,
+            "Aufdeckzustand merken",
+        );
+        ui.separator();
+        ui.add_enabled_ui(!self.quiz.questions.is_empty(), |ui| {
+            ui.menu_button("Fragen mischen", |ui| {
+                if ui.button("Zufällig").clicked() {
+                    self.reorder_questions(ui.ctx(), |q| shuffle(q));
+                    ui.close_menu();
+                }
+                let spread = ui
+                    .button("Themen verteilen")
+                    .on_hover_text("Fragen mit demselben ersten Tag möglichst weit auseinander");
+                if spread.clicked() {
+                    self.reorder_questions(ui.ctx(), shuffle_spread_topics);
+                    ui.close_menu();
                 }
-                match self.show.answer {
-                    true => ui.label(&question.answer),
-                    false => ui.label(""),
+            });
+        });
+        ui.checkbox(&mut self.shuffle_hints, "Hinweise mischen");
+        ui.horizontal(|ui| {
+            let vary = ui
+                .checkbox(&mut self.vary_hints, "Hinweise variieren, Startwert:")
+                .on_hover_text("Derselbe Startwert ergibt in jeder Stunde dieselbe Reihenfolge");
+            let seed = ui.add_enabled(self.vary_hints, egui::DragValue::new(&mut self.hint_seed));
+            if vary.changed() || seed.changed() {
+                self.order_hints();
+            }
+        });
+        ui.checkbox(&mut self.auto_show_question, "Frage automatisch anzeigen");
+        ui.horizontal(|ui| {
+            ui.label("Antwort frühestens nach (Sekunden):");
+            ui.add(egui::DragValue::new(&mut self.answer_delay).range(0.0..=600.0));
+        });
+        ui.menu_button("Tasten", |ui| self.keys.ui(ui));
+        ui.checkbox(
+            &mut self.advance_on_time_limit,
+            "Nach Ablauf der Zeit weiterblättern",
+        );
+        ui.horizontal(|ui| {
+            let mut advance = self.advance_after_answer.is_some();
+            if ui
+                .checkbox(&mut advance, "Nach Antwort automatisch weiter (Sekunden):")
+                .changed()
+            {
+                self.advance_after_answer = advance.then_some(3.0);
+            }
+            if let Some(delay) = &mut self.advance_after_answer {
+                ui.add(egui::DragValue::new(delay).range(0.0..=600.0));
+            }
+        });
+        ui.horizontal(|ui| {
+            let mut limited = self.reveal_budget.is_some();
+            let checkbox = ui
+                .checkbox(&mut limited, "Aufdeckbudget:")
+                .on_hover_text("Jeder Hinweis und jede Antwort kostet eins");
+            if checkbox.changed() {
+                self.reveal_budget = limited.then_some(DEFAULT_REVEAL_BUDGET);
+                self.reveals_left = DEFAULT_REVEAL_BUDGET;
+            }
+            if let Some(budget) = &mut self.reveal_budget {
+                if ui
+                    .add(egui::DragValue::new(budget).range(1..=999))
+                    .changed()
+                {
+                    self.reveals_left = self.reveals_left.min(*budget);
+                }
+                if ui.button("Neu starten").clicked() {
+                    self.reveals_left = *budget;
+                    self.quiz.question_nr = self.quiz.first().unwrap_or(0);
+                    self.reset_reveals(ui.ctx());
+                    ui.close_menu();
+                }
+            }
+        });
+        if ui
+            .button("aktuellen Aufdeckzustand als Standard merken")
+            .clicked()
+        {
+            // Letters are revealed one at a time for a particular answer, not by default.
+            self.default_show = Show {
+                answer_letters: 0,
+                ..self.quiz.show
+            };
+            ui.close_menu();
+        }
+        if ui.button("Aufdeckzustand-Standard zurücksetzen").clicked() {
+            self.default_show = Default::default();
+            ui.close_menu();
+        }
+        let clear_counts = egui::Button::new("Aufdeckzähler dieses Quiz zurücksetzen");
+        if ui
+            .add_enabled(!self.quiz.questions.is_empty(), clear_counts)
+            .clicked()
+        {
+            self.reveal_counts.clear(self.quiz_key);
+            ui.close_menu();
+        }
+        ui.horizontal(|ui| {
+            ui.label("Nach der letzten Frage:");
+            ui.selectable_value(&mut self.end_behavior, EndBehavior::Stop, "anhalten");
+            ui.selectable_value(&mut self.end_behavior, EndBehavior::Loop, "von vorne");
+            ui.selectable_value(
+                &mut self.end_behavior,
+                EndBehavior::Summary,
+                "Zusammenfassung",
+            );
+        });
+        let mut exam = self.exam.is_some();
+        let exam_checkbox = ui
+            .add_enabled(
+                !self.quiz.questions.is_empty(),
+                egui::Checkbox::new(&mut exam, "Prüfungsmodus"),
+            )
+            .on_hover_text(
+                "Zu jeder Frage gibt es nur ein Antwortfeld. Hinweise, Antwort, \
+                 Buchstabe zeigen, die Antworttasten, der Lösungsbogen, die Fragensuche und der \
+                 QR-Code sind aus. Nach der letzten Frage oder mit „Prüfung abgeben“ werden die \
+                 Eingaben neben den Antworten gezeigt.",
+            );
+        if exam_checkbox.changed() {
+            let len = self.quiz.questions.len();
+            self.exam = exam.then(|| Exam::new(len));
+            // The editor shows every answer.
+            self.editing &= !exam;
+            ui.close_menu();
+        }
+        ui.separator();
+        let compare = egui::Button::new("Vergleichen…");
+        if ui
+            .add_enabled(
+                !self.quiz.questions.is_empty() && self.exam.is_none(),
+                compare,
+            )
+            .clicked()
+        {
+            // A comparison leaves the quiz alone, so it doesn't supersede a load.
+            compare_dialog(
+                self.file_io.0.clone(),
+                ui.ctx().clone(),
+                self.load_generation,
+            );
+            ui.close_menu();
+        }
+        let edit = egui::Checkbox::new(&mut self.editing, "Bearbeiten");
+        let shortcut = ui.ctx().format_shortcut(&EDIT_SHORTCUT);
+        ui.add_enabled(!self.quiz.questions.is_empty() && self.exam.is_none(), edit)
+            .on_hover_text(format!("Umschalten mit {shortcut}"));
+        let find_replace = egui::Button::new("Suchen und ersetzen…");
+        if ui
+            .add_enabled(!self.quiz.questions.is_empty(), find_replace)
+            .clicked()
+        {
+            self.find_replace.get_or_insert_with(Default::default);
+            ui.close_menu();
+        }
+    }
+
+    /// Handles the messages of the background tasks reading quiz files.
+    fn receive_files(&mut self, ctx: &Context) {
+        while let Ok(msg) = self.file_io.1.try_recv() {
+            match msg {
+                FileMsg::Reading(generation) => self.loading |= generation == self.load_generation,
+                FileMsg::Error(err) => {
+                    self.loading = false;
+                    self.toasts
+                        .error(ctx, format!("Quiz konnte nicht geladen werden: {err}"));
+                }
+                FileMsg::Saved(name) => self.toasts.success(ctx, format!("Gespeichert: {name}")),
+                FileMsg::QuizSaved(name) => {
+                    self.unsaved_changes = false;
+                    self.toasts.success(ctx, format!("Gespeichert: {name}"));
+                }
+                FileMsg::SaveError(err) => self.toasts.error(ctx, err),
+                FileMsg::Compare { name, bytes } => {
+                    self.loading = false;
+                    let other = load_questions(&name, &bytes, &self.import_options);
+                    match (other, loaded(&self.quiz.questions)) {
+                        (Ok((other, _)), Some(questions)) => {
+                            self.comparison = Some(QuizDiff::new(name, questions, &other));
+                        }
+                        (Ok(_), None) => {}
+                        (Err(err), _) => self.toasts.error(ctx, format!("{name}: {err}")),
+                    }
+                }
+                FileMsg::Session {
+                    name,
+                    bytes,
+                    generation,
+                } => {
+                    if generation != self.load_generation {
+                        continue;
+                    }
+                    self.loading = false;
+                    match SessionSnapshot::from_json(&bytes) {
+                        Ok(snapshot) => {
+                            self.toasts.success(ctx, format!("Sitzung geladen: {name}"));
+                            self.restore_session(ctx, snapshot);
+                        }
+                        Err(err) => self.toasts.error(ctx, format!("{name}: {err}")),
+                    }
+                }
+                FileMsg::Loaded {
+                    name,
+                    bytes,
+                    path,
+                    playlist_entry,
+                    keep_position,
+                    generation,
+                } => {
+                    // Only the quiz loaded last counts, even if an earlier, larger file
+                    // finishes reading after it. The spinner stays for the newer load.
+                    if generation != self.load_generation {
+                        continue;
+                    }
+                    // There is no file to watch for changes on the web.
+                    #[cfg(target_arch = "wasm32")]
+                    let _ = path;
+                    self.loading = false;
+                    match load_questions(&name, &bytes, &self.import_options) {
+                        Ok((quiz, format)) => {
+                            self.unsaved_changes = false;
+                            self.toasts.success(ctx, format!("Geladen: {name}"));
+                            self.detected_format =
+                                (!format.matches_extension(&name)).then_some(format);
+                            #[cfg(not(target_arch = "wasm32"))]
+                            if path.is_some() {
+                                self.last_file.clone_from(&path);
+                            }
+                            self.stashed_quiz = Some(StashedQuiz {
+                                questions: std::mem::replace(&mut self.quiz.questions, quiz),
+                                question_nr: std::mem::take(&mut self.quiz.question_nr),
+                                playlist_entry: std::mem::replace(
+                                    &mut self.playlist.current,
+                                    playlist_entry,
+                                ),
+                                #[cfg(not(target_arch = "wasm32"))]
+                                source: std::mem::replace(
+                                    &mut self.source,
+                                    path.map(SourceFile::new),
+                                ),
+                            });
+                            if keep_position {
+                                if let Some(stashed) = &self.stashed_quiz {
+                                    self.quiz.question_nr = stashed.question_nr;
+                                }
+                            }
+                            self.clamp_question_nr();
+                            self.reset_reveals(ctx);
+                        }
+                        Err(err) => self.toasts.error(
+                            ctx,
+                            format!("Quiz konnte nicht geladen werden: {name}: {err}"),
+                        ),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Saves the reply to "Screenshot speichern…" once it arrives, cropped to the question.
+    fn receive_screenshot(&mut self, ctx: &Context) {
+        if std::mem::take(&mut self.screenshot_requested) {
+            // The rect goes along so the reply, which arrives a frame or two later, knows what
+            // to crop to.
+            let user_data = egui::UserData::new(self.central_rect);
+            ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot(user_data));
+        }
+        let screenshot = ctx.input(|i| {
+            i.events.iter().find_map(|event| match event {
+                egui::Event::Screenshot {
+                    image, user_data, ..
+                } => Some((image.clone(), user_data.clone())),
+                _ => None,
+            })
+        });
+        let Some((image, user_data)) = screenshot else {
+            return;
+        };
+        let rect = user_data
+            .data
+            .as_ref()
+            .and_then(|data| data.downcast_ref::<egui::Rect>())
+            .copied()
+            .unwrap_or(egui::Rect::EVERYTHING);
+        match png_of_region(&image, rect, ctx.pixels_per_point()) {
+            Ok(png) => {
+                let tx = self.file_io.0.clone();
+                save_dialog(tx, ctx.clone(), "frage.png", png, FileMsg::Saved);
+            }
+            Err(err) => self
+                .toasts
+                .error(ctx, format!("Screenshot fehlgeschlagen: {err}")),
+        }
+    }
+
+    /// One dot per question showing how far it was revealed. Clicking a dot jumps there.
+    fn progress_dots(&mut self, ui: &mut egui::Ui) {
+        let len = self.quiz.questions.len();
+        egui::ScrollArea::horizontal().show(ui, |ui| {
+            ui.spacing_mut().item_spacing.x = 3.0;
+            for nr in 0..len {
+                let show = match nr == self.quiz.question_nr {
+                    true => Some(self.quiz.show),
+                    false => self.reveals.get(nr).copied().flatten(),
+                };
+                let color = match show {
+                    Some(show) if show.answer => {
+                        self.accent.unwrap_or(ui.visuals().selection.bg_fill)
+                    }
+                    Some(_) => ui.visuals().weak_text_color(),
+                    None => ui.visuals().widgets.inactive.bg_fill,
                 };
+                let (rect, response) =
+                    ui.allocate_exact_size(egui::vec2(8.0, 8.0), egui::Sense::click());
+                ui.painter().circle_filled(rect.center(), 4.0, color);
+                if nr == self.quiz.question_nr {
+                    let stroke = ui.visuals().widgets.active.fg_stroke;
+                    ui.painter().circle_stroke(rect.center(), 5.0, stroke);
+                }
+                if response.on_hover_text(format!("Frage {nr}")).clicked() {
+                    self.quiz.question_nr = nr;
+                }
+            }
+        });
+    }
+
+    fn comparison_window(&mut self, ctx: &Context) {
+        // The comparison lists the answers.
+        let (None, Some(comparison)) = (&self.exam, &self.comparison) else {
+            return;
+        };
+        let mut open = true;
+        egui::Window::new(format!("Vergleich mit {}", comparison.name))
+            .id(egui::Id::new("comparison_window"))
+            .open(&mut open)
+            .default_width(600.0)
+            .show(ctx, |ui| comparison.ui(ui));
+        if !open {
+            self.comparison = None;
+        }
+    }
+
+    /// Loads a quiz pasted as text, e.g. JSON copied from a chat or website.
+    fn paste_window(&mut self, ctx: &Context) {
+        let Some(text) = &mut self.pasted_quiz else {
+            return;
+        };
+        let mut open = true;
+        let mut load = false;
+        egui::Window::new("Aus Zwischenablage einfügen")
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label("Quiz hier einfügen (Strg+V):");
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        ui.add(
+                            egui::TextEdit::multiline(text)
+                                .code_editor()
+                                .desired_width(f32::INFINITY),
+                        );
+                    });
+                load = ui
+                    .add_enabled(!text.trim().is_empty(), egui::Button::new("Als Quiz laden"))
+                    .clicked();
+            });
+        if load {
+            let msg = FileMsg::Loaded {
+                name: "Zwischenablage".to_owned(),
+                bytes: std::mem::take(text).into_bytes(),
+                path: None,
+                playlist_entry: None,
+                keep_position: false,
+                generation: self.start_load(),
             };
+            let _ = self.file_io.0.send(msg);
+            ctx.request_repaint();
+            open = false;
+        }
+        if !open {
+            self.pasted_quiz = None;
+        }
+    }
+
+    fn find_replace_window(&mut self, ctx: &Context) {
+        let Some(find_replace) = &mut self.find_replace else {
+            return;
+        };
+        let mut open = true;
+        let mut run = false;
+        egui::Window::new("Suchen und ersetzen")
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| run = find_replace.ui(ui));
+        if let (true, Some(questions)) = (run, loaded(&self.quiz.questions)) {
+            let mut edited = questions.to_vec();
+            let replaced = find_replace.apply(&mut edited);
+            find_replace.replaced = Some(replaced);
+            if replaced > 0 {
+                self.stashed_quiz = Some(StashedQuiz {
+                    questions: std::mem::replace(&mut self.quiz.questions, edited),
+                    question_nr: self.quiz.question_nr,
+                    playlist_entry: self.playlist.current,
+                    #[cfg(not(target_arch = "wasm32"))]
+                    source: self.source.clone(),
+                });
+                self.unsaved_changes = true;
+                self.reset_reveals(ctx);
+            }
+        }
+        if !open {
+            self.find_replace = None;
+        }
+    }
+
+    fn qr_code_window(&mut self, ctx: &Context) {
+        let (true, None, Some(question)) = (self.qr_code, &self.exam, self.quiz.current()) else {
+            return;
+        };
+        egui::Window::new("QR-Code")
+            .open(&mut self.qr_code)
+            .resizable(false)
+            .show(ctx, |ui| qr_code_ui(ui, &question.question));
+    }
+
+    /// Warns that the quiz can't be restored next time because the browser's storage is full.
+    #[cfg(target_arch = "wasm32")]
+    fn storage_full_banner(&mut self, ctx: &Context) {
+        if !self.storage_full {
+            return;
+        }
+        let mut dismissed = false;
+        egui::TopBottomPanel::top("storage_full_panel").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                dismissed = ui.small_button("✖").clicked();
+                let text = "Der Browserspeicher ist voll, das Quiz wird beim nächsten Öffnen \
+                            fehlen. Über Datei → Sitzung exportieren… lässt es sich sichern.";
+                ui.colored_label(ui.visuals().warn_fg_color, text);
+            });
+        });
+        if dismissed {
+            self.storage_full = false;
+        }
+    }
+
+    /// Shown instead of the quiz after a frame panicked.
+    fn panic_screen(&mut self, ctx: &Context) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading("Ein Fehler ist aufgetreten");
+            if let Some(message) = &self.panic_message {
+                ui.label(RichText::new(message).monospace());
+            }
+            ui.horizontal(|ui| {
+                if ui.button("Weiter versuchen").clicked() {
+                    self.panic_message = None;
+                }
+                if ui.button("Zustand zurücksetzen").clicked() {
+                    *self = Default::default();
+                    apply_visuals(ui.ctx(), self.accent);
+                }
+            });
+        });
+    }
+
+    /// Reveals the answer and advances to the next question on the slideshow's schedule. It
+    /// pauses while editing.
+    fn run_slideshow(&mut self, ctx: &Context) {
+        if self.slideshow.is_none() || self.is_editing() {
+            return;
+        }
+        if !ctx.wants_keyboard_input() && ctx.input(|i| i.key_pressed(egui::Key::Space)) {
+            self.toggle_slideshow_pause(ctx);
+        }
+        let (Some(config), false) = (self.slideshow, self.quiz.questions.is_empty()) else {
+            return;
+        };
+        if self.slideshow_paused.is_some() {
+            return;
+        }
+
+        let now = ctx.input(|i| i.time);
+        let elapsed = (now - self.question_arrived) as f32;
+        let reveal_at = config.interval - config.reveal_answer;
+        if config.reveal_answer > 0.0 && elapsed >= reveal_at {
+            self.quiz.show.answer = true;
+        }
+        if elapsed >= config.interval {
+            let next = self.quiz.neighbor(true).or_else(|| self.quiz.first());
+            self.quiz.question_nr = next.unwrap_or(self.quiz.question_nr);
+            // Restart the interval even if there is only one question to show.
+            self.question_arrived = now;
+        }
+
+        let next_event = match elapsed < reveal_at {
+            true => reveal_at,
+            false => config.interval,
+        };
+        ctx.request_repaint_after_secs((next_event - elapsed).max(0.0));
+    }
+
+    /// Reveals the answer when the time limit of the current question runs out, and moves on
+    /// a little later if [`Self::advance_on_time_limit`] is set. The slideshow has its own timing,
+    /// and the clock holds while the question is edited. An exam keeps the answer hidden.
+    fn run_time_limit(&mut self, ctx: &Context) {
+        if self.slideshow.is_some() || self.is_editing() {
+            return;
+        }
+        let Some(limit) = self.quiz.current().and_then(|q| q.time_limit) else {
+            return;
+        };
+        let limit = f64::from(limit);
+        let elapsed = ctx.input(|i| i.time) - self.question_arrived;
+        if elapsed < limit {
+            // Keep the ring moving.
+            ctx.request_repaint_after_secs(0.1);
+            return;
+        }
+        if self.exam.is_none() {
+            self.quiz.show.answer = true;
+        }
+        if !self.advance_on_time_limit {
+            return;
+        }
+        if elapsed >= limit + TIME_LIMIT_ANSWER_SECS {
+            match self.quiz.neighbor(true) {
+                Some(next) => self.quiz.question_nr = next,
+                None if !self.time_limit_ended_quiz => {
+                    self.reach_end();
+                    self.time_limit_ended_quiz = true;
+                }
+                None => {}
+            }
+        } else {
+            ctx.request_repaint_after_secs((limit + TIME_LIMIT_ANSWER_SECS - elapsed) as f32);
+        }
+    }
+
+    /// Moves on once the answer was shown for [`Self::advance_after_answer`] seconds. The
+    /// slideshow and the exam have their own pace, and it doesn't move on while editing.
+    fn run_advance_after_answer(&mut self, ctx: &Context) {
+        let Some(delay) = self.advance_after_answer else {
+            return;
+        };
+        if self.slideshow.is_some()
+            || self.exam.is_some()
+            || self.is_editing()
+            || self.advanced_after_answer
+        {
+            return;
+        }
+        let now = ctx.input(|i| i.time);
+        // The time limit may have revealed the answer just now.
+        self.track_answer_time(now);
+        let Some(shown_at) = self.answer_shown_at else {
+            return;
+        };
+        let shown_for = (now - shown_at) as f32;
+        if shown_for < delay {
+            ctx.request_repaint_after_secs(delay - shown_for);
+            return;
+        }
+        // Stays set on the last question, so e.g. the summary isn't reopened every frame.
+        self.advanced_after_answer = true;
+        self.step_question(true);
+    }
+
+    fn toggle_slideshow_pause(&mut self, ctx: &Context) {
+        let now = ctx.input(|i| i.time);
+        match self.slideshow_paused.take() {
+            Some(elapsed) => self.question_arrived = now - elapsed,
+            None => self.slideshow_paused = Some(now - self.question_arrived),
+        }
+    }
+
+    /// Loads the quiz of playlist entry `i` through the regular file loading path.
+    fn play_playlist_entry(&mut self, i: usize) {
+        let generation = self.start_load();
+        let Some(entry) = self.playlist.entries.get(i) else {
+            return;
+        };
+        let msg = match entry.read() {
+            Some(text) => FileMsg::Loaded {
+                name: entry.name.clone(),
+                bytes: text.into_bytes(),
+                path: entry.path.clone(),
+                playlist_entry: Some(i),
+                keep_position: false,
+                generation,
+            },
+            None => FileMsg::Error(format!("{} konnte nicht gelesen werden", entry.name)),
+        };
+        let _ = self.file_io.0.send(msg);
+    }
+
+    /// Keeps `question_nr` pointing at an existing question, e.g. when the persisted state
+    /// refers to a longer quiz than the one loaded now.
+    fn clamp_question_nr(&mut self) {
+        if let Some(questions) = loaded(&self.quiz.questions) {
+            self.quiz.question_nr = self.quiz.question_nr.min(questions.len().saturating_sub(1));
+        }
+    }
+
+    /// Restores the quiz that was open before the last file load.
+    fn undo_load(&mut self, ctx: &Context) {
+        if let Some(stashed) = self.stashed_quiz.take() {
+            self.quiz.questions = stashed.questions;
+            self.quiz.question_nr = stashed.question_nr;
+            self.playlist.current = stashed.playlist_entry;
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                self.source = stashed.source;
+            }
+            self.clamp_question_nr();
+            self.reset_reveals(ctx);
+        }
+    }
+
+    /// Sets up the reveal state for the question that is now shown.
+    fn enter_question(&mut self, ctx: &Context) {
+        self.question_arrived = ctx.input(|i| i.time);
+        self.time_limit_ended_quiz = false;
+        self.answer_shown_at = None;
+        self.answer_seconds_before = 0.0;
+        self.advanced_after_answer = false;
+        self.answer_edit = None;
+        let remembered = self.reveals.get(self.quiz.question_nr).copied().flatten();
+        self.quiz.show = match (self.reveal_mode, remembered) {
+            (RevealMode::Remember, Some(show)) => show,
+            _ => {
+                let mut show = self.default_show;
+                show.question |= self.auto_show_question;
+                show
+            }
+        };
+        self.order_hints();
+        // An answer remembered as revealed was already counted.
+        self.answer_counted = self.quiz.show.answer;
+    }
+
+    /// Changes the order of the questions and starts at the first one. This can be undone.
+    fn reorder_questions(&mut self, ctx: &Context, reorder: impl FnOnce(&mut Vec<Question>)) {
+        let Some(questions) = loaded(&self.quiz.questions) else {
+            return;
+        };
+        let mut reordered = questions.to_vec();
+        reorder(&mut reordered);
+        self.stashed_quiz = Some(StashedQuiz {
+            questions: std::mem::replace(&mut self.quiz.questions, reordered),
+            question_nr: std::mem::take(&mut self.quiz.question_nr),
+            playlist_entry: self.playlist.current,
+            #[cfg(not(target_arch = "wasm32"))]
+            source: self.source.clone(),
+        });
+        self.unsaved_changes = true;
+        self.reset_reveals(ctx);
+    }
+
+    /// Starts a quiz with a single empty question and opens it for editing.
+    fn new_quiz(&mut self, ctx: &Context) {
+        self.stashed_quiz = Some(StashedQuiz {
+            questions: std::mem::replace(&mut self.quiz.questions, vec![Question::default()]),
+            question_nr: std::mem::take(&mut self.quiz.question_nr),
+            playlist_entry: self.playlist.current.take(),
+            #[cfg(not(target_arch = "wasm32"))]
+            source: self.source.take(),
+        });
+        self.editing = true;
+        self.focus_question_field = true;
+        self.unsaved_changes = true;
+        self.reset_reveals(ctx);
+    }
+
+    fn save_quiz(&self, ctx: &Context) {
+        if let Some(questions) = loaded(&self.quiz.questions) {
+            let json = serde_json::to_vec_pretty(questions).expect("questions can be serialized");
+            let tx = self.file_io.0.clone();
+            save_dialog(tx, ctx.clone(), "quiz.json", json, FileMsg::QuizSaved);
+        }
+    }
+
+    /// The current question as text fields, with buttons to add and remove questions.
+    fn edit_ui(&mut self, ui: &mut egui::Ui) {
+        let questions = &mut self.quiz.questions;
+        if questions.is_empty() {
+            return;
+        }
+        let mut edited_list = false;
+        let mut save = false;
+        ui.horizontal(|ui| {
+            ui.label("Frage: ");
+            if ui.button("<<").clicked() {
+                self.quiz.question_nr = self.quiz.question_nr.saturating_sub(1);
+            }
+            ui.add(
+                egui::DragValue::new(&mut self.quiz.question_nr)
+                    .range(0..=questions.len().saturating_sub(1)),
+            );
+            if ui.button(">>").clicked() {
+                self.quiz.question_nr = (self.quiz.question_nr + 1).min(questions.len() - 1);
+            }
+            ui.separator();
+            if ui.button("Frage hinzufügen").clicked() {
+                self.quiz.question_nr += 1;
+                questions.insert(self.quiz.question_nr, Question::default());
+                self.focus_question_field = true;
+                edited_list = true;
+            }
+            let delete = egui::Button::new("Frage löschen");
+            if ui.add_enabled(questions.len() > 1, delete).clicked() {
+                questions.remove(self.quiz.question_nr);
+                self.quiz.question_nr = self.quiz.question_nr.min(questions.len() - 1);
+                edited_list = true;
+            }
+            ui.separator();
+            save = ui.button("Quiz speichern…").clicked();
+            if ui.button("Fertig").clicked() {
+                self.editing = false;
+            }
         });
+        ui.separator();
+        if let Some(question) = questions.get_mut(self.quiz.question_nr) {
+            let focus = std::mem::take(&mut self.focus_question_field);
+            self.unsaved_changes |= edit_question_ui(ui, question, focus);
+        }
+        if edited_list {
+            self.unsaved_changes = true;
+            self.reset_reveals(ui.ctx());
+        }
+        if save {
+            self.save_quiz(ui.ctx());
+        }
+    }
+
+    fn session_snapshot(&self) -> Option<SessionSnapshot> {
+        Some(SessionSnapshot {
+            questions: loaded(&self.quiz.questions)?.to_vec(),
+            question_nr: self.quiz.question_nr,
+            show: self.quiz.show,
+            reveals: self.reveals.clone(),
+            tag_filter: self.quiz.tag_filter.clone(),
+        })
+    }
+
+    /// Continues the session in `snapshot`. Like loading a file, this can be undone.
+    fn restore_session(&mut self, ctx: &Context, snapshot: SessionSnapshot) {
+        self.stashed_quiz = Some(StashedQuiz {
+            questions: std::mem::replace(&mut self.quiz.questions, snapshot.questions),
+            question_nr: std::mem::replace(&mut self.quiz.question_nr, snapshot.question_nr),
+            playlist_entry: self.playlist.current.take(),
+            #[cfg(not(target_arch = "wasm32"))]
+            source: self.source.take(),
+        });
+        self.quiz.tag_filter = snapshot.tag_filter;
+        self.clamp_question_nr();
+        self.reset_reveals(ctx);
+        let len = self.reveals.len();
+        self.reveals = snapshot.reveals;
+        self.reveals.resize(len, None);
+        self.quiz.show = snapshot.show;
+        self.answer_counted = self.quiz.show.answer;
+    }
+
+    /// Picks [`Self::hint_order`] for the current question.
+    fn order_hints(&mut self) {
+        match self.vary_hints {
+            true => {
+                let hash =
+                    crate::stable_hash(&format!("{}:{}", self.hint_seed, self.quiz.question_nr));
+                self.hint_order = match hash % 2 {
+                    0 => [0, 1],
+                    _ => [1, 0],
+                };
+            }
+            false => fastrand::shuffle(&mut self.hint_order),
+        }
+    }
+
+    /// Forgets what was revealed of the previous quiz.
+    fn reset_reveals(&mut self, ctx: &Context) {
+        let len = self.quiz.questions.len();
+        self.reveals = vec![None; len];
+        self.quiz.results = vec![None; len];
+        self.quiz_key = loaded(&self.quiz.questions).map_or(0, RevealCounts::quiz_key);
+        self.summary = false;
+        if self.exam.is_some() {
+            self.exam = Some(Exam::new(len));
+        }
+        self.prev_question_nr = self.quiz.question_nr;
+        self.enter_question(ctx);
     }
 }
 
-fn file_dialog(tx: Sender<String>, ctx: Context) {
+/// `questions`, or `None` while no quiz is loaded.
+fn loaded(questions: &[Question]) -> Option<&[Question]> {
+    (!questions.is_empty()).then_some(questions)
+}
+
+/// `text` with its lines joined by spaces when `collapse` is set, otherwise unchanged.
+fn collapse_lines(text: &str, collapse: bool) -> Cow<'_, str> {
+    match collapse && text.contains('\n') {
+        true => Cow::Owned(
+            text.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join(" "),
+        ),
+        false => Cow::Borrowed(text),
+    }
+}
+
+/// `text` in the body font, made smaller until it fits into half of the screen's height so the
+/// hints and answer stay visible on a projector.
+fn fitted_text(ui: &egui::Ui, text: &str) -> RichText {
+    const MIN_SIZE: f32 = 8.0;
+    let max_height = ui.ctx().screen_rect().height() / 2.0;
+    let wrap_width = ui.available_width();
+    let color = ui.visuals().text_color();
+    let mut font_id = egui::TextStyle::Body.resolve(ui.style());
+    while font_id.size > MIN_SIZE {
+        let galley =
+            ui.fonts(|fonts| fonts.layout(text.to_owned(), font_id.clone(), color, wrap_width));
+        if galley.size().y <= max_height {
+            break;
+        }
+        font_id.size = (font_id.size - 1.0).max(MIN_SIZE);
+    }
+    RichText::new(text).font(font_id)
+}
+
+/// A ring that empties as the `limit` seconds of a question run out.
+fn time_limit_ring(ui: &mut egui::Ui, elapsed: f64, limit: u32) {
+    let remaining = (f64::from(limit) - elapsed).max(0.0);
+    let fraction = match limit {
+        0 => 0.0,
+        _ => (remaining / f64::from(limit)) as f32,
+    };
+    let size = ui.spacing().interact_size.y;
+    let (rect, response) = ui.allocate_exact_size(egui::Vec2::splat(size), egui::Sense::hover());
+    let radius = size / 2.0 - 2.0;
+    let painter = ui.painter();
+    let track = egui::Stroke::new(3.0, ui.visuals().widgets.inactive.bg_fill);
+    painter.circle_stroke(rect.center(), radius, track);
+    // Clockwise from the top, like a clock hand.
+    let steps = (fraction * 64.0).ceil() as usize;
+    let points: Vec<egui::Pos2> = (0..=steps)
+        .map(|step| {
+            let angle = std::f32::consts::TAU * fraction * step as f32 / steps.max(1) as f32;
+            rect.center() + radius * egui::vec2(angle.sin(), -angle.cos())
+        })
+        .collect();
+    let color = match remaining <= 5.0 {
+        true => ui.visuals().warn_fg_color,
+        false => ui.visuals().selection.bg_fill,
+    };
+    if steps > 0 {
+        painter.add(egui::Shape::line(points, egui::Stroke::new(3.0, color)));
+    }
+    response.on_hover_text(format!("noch {:.0} s", remaining.ceil()));
+}
+
+/// Sets egui's own dark and light visuals, using `accent` for selections, links and pressed
+/// widgets in both if there is one.
+fn apply_visuals(ctx: &Context, accent: Option<Color32>) {
+    ctx.set_visuals_of(egui::Theme::Dark, egui::Visuals::dark());
+    ctx.set_visuals_of(egui::Theme::Light, egui::Visuals::light());
+    let Some(color) = accent else {
+        return;
+    };
+    ctx.all_styles_mut(|style| {
+        let visuals = &mut style.visuals;
+        visuals.selection.bg_fill = color;
+        visuals.hyperlink_color = color;
+        visuals.widgets.active.bg_fill = color;
+        visuals.widgets.active.weak_bg_fill = color;
+    });
+}
+
+/// Hides all but the first `revealed` letters of every word of `answer`, hangman style.
+///
+/// `answer_mask("Neu Delhi", 1)` gives `"N _ _   D _ _ _ _"`. Punctuation is never hidden.
+fn answer_mask(answer: &str, revealed: usize) -> String {
+    answer
+        .split_whitespace()
+        .map(|word| {
+            word.chars()
+                .enumerate()
+                .map(|(i, c)| match i < revealed || !c.is_alphanumeric() {
+                    true => c.to_string(),
+                    false => "_".to_owned(),
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("   ")
+}
+
+/// Shown while no quiz is loaded, so new users know where to start. Returns whether "Quiz
+/// öffnen" and "Beispielquiz laden" were clicked.
+fn empty_state_ui(ui: &mut egui::Ui) -> (bool, bool) {
+    let mut open_clicked = false;
+    let mut example_clicked = false;
+    ui.vertical_centered(|ui| {
+        ui.add_space(ui.available_height() / 4.0);
+        ui.heading("Noch kein Quiz geladen");
+        ui.add_space(8.0);
+        let open = egui::Button::new(RichText::new("Quiz öffnen").heading());
+        open_clicked = ui.add(open).clicked();
+        example_clicked = ui.button("Beispielquiz laden").clicked();
+        ui.add_space(8.0);
+        ui.label(
+            RichText::new(
+                "Unterstützt werden JSON-Dateien mit einer Liste von Fragen mit den Feldern \
+                 \"question\" und \"answer\" (auch eine Liste mehrerer richtiger Antworten) \
+                 sowie den optionalen \"hint1\" und \"hint2\". Außerdem Excel-, CSV- und \
+                 TSV-Tabellen mit den Spalten Frage, Hinweis 1, Hinweis 2 und Antwort und \
+                 nummerierte Listen, in denen jede Frage eine Zeile \"Antwort: …\" hat.",
+            )
+            .weak(),
+        );
+    });
+    (open_clicked, example_clicked)
+}
+
+/// Loads the quiz built into the app, which works without any file or network access.
+fn load_example_quiz(tx: &Sender<FileMsg>, ctx: &Context, generation: u64) {
+    load_embedded_quiz(tx, ctx, generation, "Beispielquiz.json", EXAMPLE_QUIZ);
+}
+
+/// Loads `text` like a file called `name`.
+fn load_embedded_quiz(
+    tx: &Sender<FileMsg>,
+    ctx: &Context,
+    generation: u64,
+    name: &str,
+    text: &str,
+) {
+    let msg = FileMsg::Loaded {
+        name: name.to_owned(),
+        bytes: text.as_bytes().to_vec(),
+        path: None,
+        playlist_entry: None,
+        keep_position: false,
+        generation,
+    };
+    let _ = tx.send(msg);
+    ctx.request_repaint();
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl MyApp {
+    /// Asks whether to reload the quiz once its file was changed on disk.
+    fn source_file_prompt(&mut self, ctx: &Context) {
+        let Some(source) = self.source.as_mut() else {
+            return;
+        };
+        source.poll(ctx);
+        if !source.changed {
+            return;
+        }
+
+        egui::TopBottomPanel::top("file_changed_panel").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Datei wurde geändert – neu laden?");
+                if ui.button("Neu laden").clicked() {
+                    source.changed = false;
+                    // Like `start_load`, which can't be called while `source` is borrowed.
+                    self.load_generation += 1;
+                    let generation = self.load_generation;
+                    let msg = read_file_msg(&source.path, self.playlist.current, generation);
+                    let _ = self.file_io.0.send(msg);
+                }
+                if ui.button("Ignorieren").clicked() {
+                    source.changed = false;
+                }
+            });
+        });
+    }
+}
+
+/// Reads the quiz file at `path` again, staying on the current question.
+#[cfg(not(target_arch = "wasm32"))]
+fn read_file_msg(
+    path: &std::path::Path,
+    playlist_entry: Option<usize>,
+    generation: u64,
+) -> FileMsg {
+    match std::fs::read(path) {
+        Ok(bytes) => FileMsg::Loaded {
+            name: path.display().to_string(),
+            bytes,
+            path: Some(path.to_owned()),
+            playlist_entry,
+            keep_position: true,
+            generation,
+        },
+        Err(err) => FileMsg::Error(format!("{}: {err}", path.display())),
+    }
+}
+
+fn file_dialog(tx: Sender<FileMsg>, ctx: Context, generation: u64) {
     let task = rfd::AsyncFileDialog::new().pick_file();
     execute(async move {
         let file = task.await;
         if let Some(file) = file {
-            let data = file.read().await;
-            if let Ok(text) = String::from_utf8(data) {
-                let _ = tx.send(text);
-                ctx.request_repaint();
-            }
+            let _ = tx.send(FileMsg::Reading(generation));
+            ctx.request_repaint();
+            let msg = FileMsg::Loaded {
+                name: file.file_name(),
+                path: file_path(&file),
+                bytes: file.read().await,
+                playlist_entry: None,
+                keep_position: false,
+                generation,
+            };
+            let _ = tx.send(msg);
+            ctx.request_repaint();
+        }
+    });
+}
+
+/// Asks where to save `bytes`, suggesting `file_name`. On the web, this downloads the file.
+/// `saved` is the message sent on success.
+fn save_dialog(
+    tx: Sender<FileMsg>,
+    ctx: Context,
+    file_name: &str,
+    bytes: Vec<u8>,
+    saved: fn(String) -> FileMsg,
+) {
+    let task = rfd::AsyncFileDialog::new()
+        .set_file_name(file_name)
+        .save_file();
+    execute(async move {
+        if let Some(file) = task.await {
+            let msg = match file.write(&bytes).await {
+                Ok(()) => saved(file.file_name()),
+                Err(err) => FileMsg::SaveError(format!("{}: {err}", file.file_name())),
+            };
+            let _ = tx.send(msg);
+            ctx.request_repaint();
+        }
+    });
+}
+
+fn compare_dialog(tx: Sender<FileMsg>, ctx: Context, generation: u64) {
+    let task = rfd::AsyncFileDialog::new().pick_file();
+    execute(async move {
+        if let Some(file) = task.await {
+            let _ = tx.send(FileMsg::Reading(generation));
+            ctx.request_repaint();
+            let msg = FileMsg::Compare {
+                name: file.file_name(),
+                bytes: file.read().await,
+            };
+            let _ = tx.send(msg);
+            ctx.request_repaint();
         }
     });
 }
 
+fn session_dialog(tx: Sender<FileMsg>, ctx: Context, generation: u64) {
+    let task = rfd::AsyncFileDialog::new().pick_file();
+    execute(async move {
+        if let Some(file) = task.await {
+            let _ = tx.send(FileMsg::Reading(generation));
+            ctx.request_repaint();
+            let msg = FileMsg::Session {
+                name: file.file_name(),
+                bytes: file.read().await,
+                generation,
+            };
+            let _ = tx.send(msg);
+            ctx.request_repaint();
+        }
+    });
+}
+
+fn playlist_dialog(tx: Sender<PlaylistEntry>, ctx: Context) {
+    let task = rfd::AsyncFileDialog::new().pick_files();
+    execute(async move {
+        for file in task.await.unwrap_or_default() {
+            let name = file.file_name();
+            let path = file_path(&file);
+            let text = String::from_utf8(file.read().await).ok();
+            let _ = tx.send(PlaylistEntry { name, path, text });
+        }
+        ctx.request_repaint();
+    });
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn file_path(file: &rfd::FileHandle) -> Option<PathBuf> {
+    Some(file.path().to_owned())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn file_path(_file: &rfd::FileHandle) -> Option<PathBuf> {
+    None
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 fn execute<F: Future<Output = ()> + Send + 'static>(f: F) {
     // this is stupid... use any executor of your choice instead
@@ -182,3 +2478,50 @@ fn execute<F: Future<Output = ()> + Send + 'static>(f: F) {
 fn execute<F: Future<Output = ()> + 'static>(f: F) {
     wasm_bindgen_futures::spawn_local(f);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reads a saved state the way [`MyApp::new`] does, minus the storage.
+    fn restore(state: serde_json::Value) -> MyApp {
+        let mut app: MyApp = serde_json::from_value(state).unwrap();
+        app = app.migrate().unwrap();
+        app.clamp_question_nr();
+        app
+    }
+
+    #[test]
+    fn saved_question_nr_is_clamped_to_the_quiz() {
+        let questions = serde_json::to_value(crate::quiz_core::tests::quiz().questions).unwrap();
+        let current = serde_json::json!({
+            "schema_version": SCHEMA_VERSION,
+            "quiz": { "questions": questions, "question_nr": 99 },
+        });
+        // Before version 2 the quiz lived at the top level.
+        let legacy = serde_json::json!({
+            "schema_version": 1,
+            "questions": questions,
+            "question_nr": 99,
+        });
+        for state in [current, legacy] {
+            let app = restore(state);
+            assert_eq!(app.quiz.questions.len(), 3);
+            assert_eq!(app.quiz.question_nr, 2);
+        }
+    }
+
+    #[test]
+    fn collapse_lines_joins_trimmed_lines() {
+        let text = "Wie heißt\n  die Hauptstadt  \n\nvon Frankreich?";
+        assert_eq!(
+            collapse_lines(text, true),
+            "Wie heißt die Hauptstadt von Frankreich?"
+        );
+        assert_eq!(collapse_lines(text, false), text);
+        assert!(matches!(
+            collapse_lines("Eine Zeile", true),
+            Cow::Borrowed(_)
+        ));
+    }
+}
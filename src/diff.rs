@@ -0,0 +1,120 @@
+use crate::quiz_core::Question;
+use egui::text::LayoutJob;
+use egui::{Color32, TextFormat};
+use similar::{ChangeTag, TextDiff};
+
+/// What changed between the loaded quiz and another version of it.
+///
+/// Questions are matched by their text, so a reworded question shows up as removed and added.
+pub struct QuizDiff {
+    /// Name of the file compared against.
+    pub name: String,
+    pub added: Vec<Question>,
+    pub removed: Vec<Question>,
+    /// Questions with the same text whose hints or answer changed, as `(old, new)`.
+    pub modified: Vec<(Question, Question)>,
+}
+
+impl QuizDiff {
+    pub fn new(name: String, old: &[Question], new: &[Question]) -> Self {
+        let find = |questions: &[Question], text: &str| {
+            questions
+                .iter()
+                .find(|q| q.question.trim() == text.trim())
+                .cloned()
+        };
+        let mut diff = Self {
+            name,
+            added: Vec::new(),
+            removed: Vec::new(),
+            modified: Vec::new(),
+        };
+        for question in old {
+            match find(new, &question.question) {
+                None => diff.removed.push(question.clone()),
+                Some(changed) if fields(question) != fields(&changed) => {
+                    diff.modified.push((question.clone(), changed));
+                }
+                Some(_) => {}
+            }
+        }
+        for question in new {
+            if find(old, &question.question).is_none() {
+                diff.added.push(question.clone());
+            }
+        }
+        diff
+    }
+
+    pub fn ui(&self, ui: &mut egui::Ui) {
+        if self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty() {
+            ui.label("Keine Unterschiede");
+            return;
+        }
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            section(ui, "Neu", &self.added);
+            section(ui, "Entfernt", &self.removed);
+            ui.collapsing(format!("Geändert ({})", self.modified.len()), |ui| {
+                for (old, new) in &self.modified {
+                    ui.label(egui::RichText::new(&old.question).strong());
+                    ui.columns(2, |columns| {
+                        columns[0].weak("Aktuelles Quiz");
+                        columns[1].weak(&self.name);
+                        let labels = ["Hinweis 1", "Hinweis 2", "Antwort"];
+                        let changes = fields(old).into_iter().zip(fields(new));
+                        for (label, (old, new)) in labels.into_iter().zip(changes) {
+                            if old == new {
+                                continue;
+                            }
+                            let [old_job, new_job] = word_diff(columns[0].style(), old, new);
+                            columns[0].label(format!("{label}:"));
+                            columns[0].label(old_job);
+                            columns[1].label(format!("{label}:"));
+                            columns[1].label(new_job);
+                        }
+                    });
+                    ui.separator();
+                }
+            });
+        });
+    }
+}
+
+/// The fields compared between two versions of a question.
+fn fields(question: &Question) -> [&str; 3] {
+    [&question.hint1, &question.hint2, &question.answer].map(String::as_str)
+}
+
+fn section(ui: &mut egui::Ui, title: &str, questions: &[Question]) {
+    ui.collapsing(format!("{title} ({})", questions.len()), |ui| {
+        for question in questions {
+            ui.label(format!("{} → {}", question.question, question.answer));
+        }
+    });
+}
+
+/// `old` with removed words and `new` with inserted words highlighted.
+fn word_diff(style: &egui::Style, old: &str, new: &str) -> [LayoutJob; 2] {
+    let font_id = egui::TextStyle::Body.resolve(style);
+    let color = style.visuals.text_color();
+    let plain = TextFormat::simple(font_id.clone(), color);
+    let highlighted = |background: Color32| TextFormat {
+        background,
+        ..TextFormat::simple(font_id.clone(), color)
+    };
+    let mut jobs = [LayoutJob::default(), LayoutJob::default()];
+    let diff = TextDiff::from_words(old, new);
+    for change in diff.iter_all_changes() {
+        let text = change.value();
+        match change.tag() {
+            ChangeTag::Equal => {
+                for job in &mut jobs {
+                    job.append(text, 0.0, plain.clone());
+                }
+            }
+            ChangeTag::Delete => jobs[0].append(text, 0.0, highlighted(Color32::DARK_RED)),
+            ChangeTag::Insert => jobs[1].append(text, 0.0, highlighted(Color32::DARK_GREEN)),
+        }
+    }
+    jobs
+}
@@ -0,0 +1,39 @@
+use crate::quiz_core::Question;
+
+/// Text fields for every part of `question`. Returns whether anything was changed.
+///
+/// With `focus`, the question field takes the keyboard focus, e.g. for a new quiz.
+pub fn edit_question_ui(ui: &mut egui::Ui, question: &mut Question, focus: bool) -> bool {
+    let mut changed = false;
+    egui::Grid::new("edit_question_grid")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Frage:");
+            let response = ui.add(
+                egui::TextEdit::multiline(&mut question.question)
+                    .desired_rows(2)
+                    .desired_width(f32::INFINITY),
+            );
+            if focus {
+                response.request_focus();
+            }
+            changed |= response.changed();
+            ui.end_row();
+            for (label, text) in [
+                ("Hinweis 1:", &mut question.hint1),
+                ("Hinweis 2:", &mut question.hint2),
+                ("Antwort:", &mut question.answer),
+            ] {
+                ui.label(label);
+                changed |= ui
+                    .add(egui::TextEdit::singleline(text).desired_width(f32::INFINITY))
+                    .changed();
+                ui.end_row();
+            }
+        });
+    if let Err(errors) = question.validate() {
+        let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        ui.colored_label(ui.visuals().warn_fg_color, errors.join(", "));
+    }
+    changed
+}
@@ -0,0 +1,67 @@
+use crate::quiz_core::Question;
+use egui::RichText;
+
+/// The responses typed in during the "Prüfungsmodus".
+///
+/// While an exam runs, hints, the answer, the answer keys, the answer sheet, the question
+/// search, the QR code and the comparison with another file are unavailable. Each question only gets a field for the response,
+/// and the answers are revealed next to the responses once the exam is handed in.
+pub struct Exam {
+    pub responses: Vec<String>,
+    /// Whether the exam was handed in and the results are shown.
+    pub finished: bool,
+}
+
+impl Exam {
+    pub fn new(questions: usize) -> Self {
+        Self {
+            responses: vec![String::new(); questions],
+            finished: false,
+        }
+    }
+
+    /// The response field for question `nr`. Returns `true` when the exam is handed in.
+    pub fn response_ui(&mut self, ui: &mut egui::Ui, nr: usize) -> bool {
+        if let Some(response) = self.responses.get_mut(nr) {
+            ui.label("Deine Antwort:");
+            ui.add(egui::TextEdit::multiline(response).desired_rows(2));
+        }
+        ui.button("Prüfung abgeben").clicked()
+    }
+
+    /// Every response next to the right answer. Returns `true` when the exam should end.
+    pub fn results_ui(&self, ui: &mut egui::Ui, questions: &[Question]) -> bool {
+        ui.heading("Ergebnis");
+        let correct = questions
+            .iter()
+            .zip(&self.responses)
+            .filter(|(question, response)| question.accepts(response))
+            .count();
+        ui.label(format!("{correct} von {} richtig", questions.len()));
+        let leave = ui.button("Prüfungsmodus beenden").clicked();
+        ui.separator();
+        egui::ScrollArea::vertical()
+            .auto_shrink(false)
+            .show(ui, |ui| {
+                egui::Grid::new("exam_results_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong("Frage");
+                        ui.strong("Deine Antwort");
+                        ui.strong("Antwort");
+                        ui.end_row();
+                        for (question, response) in questions.iter().zip(&self.responses) {
+                            ui.label(&question.question);
+                            let mark = match question.accepts(response) {
+                                true => "✔ ",
+                                false => "",
+                            };
+                            ui.label(format!("{mark}{response}"));
+                            ui.label(RichText::new(question.answers_text()).strong());
+                            ui.end_row();
+                        }
+                    });
+            });
+        leave
+    }
+}
@@ -0,0 +1,41 @@
+use crate::quiz_core::Question;
+use serde::{Deserialize, Serialize};
+
+/// How a quiz is written out for other programs.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportOptions {
+    /// Append both hints to the front of each Anki card.
+    pub anki_include_hints: bool,
+}
+
+impl ExportOptions {
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.anki_include_hints, "Hinweise mitexportieren");
+    }
+}
+
+/// The quiz as tab-separated `question<TAB>answer` lines, which Anki imports directly. The
+/// source of a question, if it has one, is added below the answer.
+///
+/// Anki reads the fields as HTML, so line breaks become `<br>` and tabs, which would start a
+/// new field, become spaces.
+pub fn anki_text(questions: &[Question], options: &ExportOptions) -> String {
+    let field = |text: &str| text.replace('\t', " ").replace('\n', "<br>");
+    let mut out = String::from("#separator:tab\n#html:true\n");
+    for question in questions {
+        out += &field(&question.question);
+        if options.anki_include_hints {
+            for (nr, hint) in [&question.hint1, &question.hint2].into_iter().enumerate() {
+                out += &format!("<br>Hinweis {}: {}", nr + 1, field(hint));
+            }
+        }
+        out += "\t";
+        out += &field(&question.answers_text());
+        if let Some(source) = &question.source {
+            out += &format!("<br>Quelle: {}", field(source));
+        }
+        out += "\n";
+    }
+    out
+}
@@ -0,0 +1,105 @@
+use crate::quiz_core::Question;
+
+/// State of the "Suchen und ersetzen" window.
+#[derive(Debug, Default)]
+pub struct FindReplace {
+    pub search: String,
+    pub replacement: String,
+    pub case_sensitive: bool,
+    /// How many replacements the last run made, shown until the search changes.
+    pub replaced: Option<usize>,
+}
+
+impl FindReplace {
+    /// Returns `true` when "Ersetzen" was clicked.
+    pub fn ui(&mut self, ui: &mut egui::Ui) -> bool {
+        egui::Grid::new("find_replace_grid").show(ui, |ui| {
+            ui.label("Suchen:");
+            if ui.text_edit_singleline(&mut self.search).changed() {
+                self.replaced = None;
+            }
+            ui.end_row();
+            ui.label("Ersetzen durch:");
+            ui.text_edit_singleline(&mut self.replacement);
+            ui.end_row();
+        });
+        ui.checkbox(&mut self.case_sensitive, "Groß-/Kleinschreibung beachten");
+        let clicked = ui
+            .add_enabled(!self.search.is_empty(), egui::Button::new("Alle ersetzen"))
+            .clicked();
+        if let Some(replaced) = self.replaced {
+            ui.label(format!("{replaced} Ersetzungen"));
+        }
+        clicked
+    }
+
+    /// Replaces every match in the question, hints and answer of all `questions`, returning
+    /// the number of replacements.
+    pub fn apply(&self, questions: &mut [Question]) -> usize {
+        let mut count = 0;
+        for question in questions {
+            for field in [
+                &mut question.question,
+                &mut question.hint1,
+                &mut question.hint2,
+                &mut question.answer,
+            ] {
+                count += replace(field, &self.search, &self.replacement, self.case_sensitive);
+            }
+            for alternative in &mut question.alternatives {
+                count += replace(
+                    alternative,
+                    &self.search,
+                    &self.replacement,
+                    self.case_sensitive,
+                );
+            }
+        }
+        count
+    }
+}
+
+/// Replaces every non-overlapping occurrence of `search` in `text`, returning how many there were.
+fn replace(text: &mut String, search: &str, replacement: &str, case_sensitive: bool) -> usize {
+    if search.is_empty() {
+        return 0;
+    }
+    let mut result = String::with_capacity(text.len());
+    let mut count = 0;
+    let mut rest = text.as_str();
+    while !rest.is_empty() {
+        match match_len(rest, search, case_sensitive) {
+            Some(len) => {
+                result += replacement;
+                rest = &rest[len..];
+                count += 1;
+            }
+            None => {
+                let c = rest.chars().next().expect("rest is not empty");
+                result.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    if count > 0 {
+        *text = result;
+    }
+    count
+}
+
+/// The length in bytes of the prefix of `text` that matches `search`, if any.
+fn match_len(text: &str, search: &str, case_sensitive: bool) -> Option<usize> {
+    if case_sensitive {
+        return text.starts_with(search).then_some(search.len());
+    }
+    let mut len = 0;
+    let mut chars = text.chars();
+    for wanted in search.chars() {
+        let c = chars.next()?;
+        if !c.to_lowercase().eq(wanted.to_lowercase()) {
+            return None;
+        }
+        len += c.len_utf8();
+    }
+    Some(len)
+}
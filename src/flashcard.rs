@@ -0,0 +1,46 @@
+use egui::{Align2, FontId, Rect, Sense};
+
+/// Seconds a flip of the card takes.
+const FLIP_SECS: f32 = 0.3;
+
+/// A card showing `front` or, once `flipped`, `back`. Flipping animates the card turning
+/// around its vertical axis: it narrows to nothing, switches sides and widens again.
+///
+/// `id_salt` tells cards apart, so moving to another card doesn't animate a flip and briefly
+/// show its back.
+pub fn flashcard_ui(
+    ui: &mut egui::Ui,
+    id_salt: impl std::hash::Hash,
+    front: &str,
+    back: &str,
+    flipped: bool,
+) -> egui::Response {
+    let id = ui.id().with(("flashcard", id_salt));
+    let t = ui.ctx().animate_bool_with_time(id, flipped, FLIP_SECS);
+    let size = egui::vec2(
+        ui.available_width().min(600.0),
+        (ui.available_height() * 0.6).clamp(120.0, 400.0),
+    );
+    let (rect, response) = ui.allocate_exact_size(size, Sense::click());
+    // The apparent width of a card turned by `t` half turns.
+    let width = (std::f32::consts::PI * t).cos().abs() * rect.width();
+    let card = Rect::from_center_size(rect.center(), egui::vec2(width, rect.height()));
+
+    let visuals = ui.visuals();
+    let (text, fill) = match t < 0.5 {
+        true => (front, visuals.extreme_bg_color),
+        false => (back, visuals.faint_bg_color),
+    };
+    let painter = ui.painter().with_clip_rect(card);
+    painter.rect(card, 8.0, fill, visuals.widgets.noninteractive.bg_stroke);
+    let font_id = FontId::proportional(egui::TextStyle::Heading.resolve(ui.style()).size);
+    let galley = painter.layout(
+        text.to_owned(),
+        font_id,
+        visuals.text_color(),
+        rect.width() - 32.0,
+    );
+    let text_rect = Align2::CENTER_CENTER.anchor_size(card.center(), galley.size());
+    painter.galley(text_rect.min, galley, visuals.text_color());
+    response.on_hover_text("Klicken oder Leertaste zum Umdrehen")
+}
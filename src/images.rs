@@ -0,0 +1,219 @@
+use base64::Engine as _;
+use egui::mutex::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[derive(Clone)]
+enum Entry {
+    /// The image is being downloaded.
+    Pending,
+    Ready(Arc<[u8]>),
+    Failed,
+}
+
+/// Image bytes of question images, keyed by the hash of their source.
+///
+/// `data:` URIs are decoded once, files are read once and `http(s)` URLs are downloaded once
+/// per session. On native, downloads are also written to disk so they survive restarts. The
+/// bytes are then handed to egui's image loader, which caches the resulting texture itself.
+#[derive(Default)]
+pub struct ImageCache {
+    entries: Arc<Mutex<HashMap<u64, Entry>>>,
+}
+
+impl ImageCache {
+    /// Shows `text` as an image if [`is_image_ref`] says it is one, else as a label.
+    pub fn show_text_or_image(&mut self, ui: &mut egui::Ui, text: &str) {
+        match is_image_ref(text) {
+            true => {
+                self.show(ui, text.trim());
+            }
+            false => {
+                ui.label(text);
+            }
+        }
+    }
+
+    /// Shows the image referenced by `source`, or a placeholder if it cannot be loaded.
+    pub fn show(&mut self, ui: &mut egui::Ui, source: &str) -> egui::Response {
+        let key = crate::stable_hash(source);
+        match self.entry(ui.ctx(), key, source) {
+            Entry::Pending => ui.spinner(),
+            Entry::Ready(bytes) => ui.add(
+                image(key, bytes)
+                    .max_width(ui.available_width())
+                    .fit_to_original_size(1.0)
+                    .sense(egui::Sense::click()),
+            ),
+            Entry::Failed => ui.colored_label(
+                ui.visuals().warn_fg_color,
+                "⚠ Bild konnte nicht geladen werden",
+            ),
+        }
+    }
+
+    /// Shows the image referenced by `source` as large as fits on the screen, over everything
+    /// else. Returns `true` once it is dismissed with a click or Escape.
+    pub fn lightbox(&mut self, ctx: &egui::Context, source: &str) -> bool {
+        let key = crate::stable_hash(source);
+        let entry = self.entry(ctx, key, source);
+        let screen = ctx.screen_rect();
+        let mut closed = ctx.input(|i| i.key_pressed(egui::Key::Escape));
+        egui::Area::new(egui::Id::new("image_lightbox"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(screen.min)
+            .show(ctx, |ui| {
+                ui.painter()
+                    .rect_filled(screen, 0.0, egui::Color32::from_black_alpha(220));
+                closed |= ui.allocate_rect(screen, egui::Sense::click()).clicked();
+                if let Entry::Ready(bytes) = entry {
+                    let size = screen.size() * 0.95;
+                    let rect = egui::Rect::from_center_size(screen.center(), size);
+                    ui.put(rect, image(key, bytes).fit_to_exact_size(size));
+                }
+            });
+        closed
+    }
+
+    /// Forgets all images, including the ones cached on disk.
+    pub fn clear(&mut self, ctx: &egui::Context) {
+        self.entries.lock().clear();
+        ctx.forget_all_images();
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(dir) = disk_cache_dir() {
+            let _ = std::fs::remove_dir_all(dir);
+        }
+    }
+
+    fn entry(&self, ctx: &egui::Context, key: u64, source: &str) -> Entry {
+        if let Some(entry) = self.entries.lock().get(&key) {
+            return entry.clone();
+        }
+        let entry = if source.starts_with("data:") {
+            match decode_data_uri(source) {
+                Some(bytes) => Entry::Ready(bytes.into()),
+                None => Entry::Failed,
+            }
+        } else if source.starts_with("http://") || source.starts_with("https://") {
+            self.download(ctx, key, source)
+        } else {
+            read_file(source)
+        };
+        // A download may already have finished, so don't overwrite its result.
+        self.entries.lock().entry(key).or_insert(entry).clone()
+    }
+
+    fn download(&self, ctx: &egui::Context, key: u64, url: &str) -> Entry {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(bytes) = disk_cache_path(key).and_then(|path| std::fs::read(path).ok()) {
+            return Entry::Ready(bytes.into());
+        }
+
+        self.entries.lock().insert(key, Entry::Pending);
+        let entries = self.entries.clone();
+        let ctx = ctx.clone();
+        ehttp::fetch(ehttp::Request::get(url), move |response| {
+            let entry = match response {
+                Ok(response) if response.ok => {
+                    #[cfg(not(target_arch = "wasm32"))]
+                    write_disk_cache(key, &response.bytes);
+                    Entry::Ready(response.bytes.into())
+                }
+                Ok(response) => {
+                    log::warn!("Failed to download {}: {}", response.url, response.status);
+                    Entry::Failed
+                }
+                Err(err) => {
+                    log::warn!("Failed to download image: {err}");
+                    Entry::Failed
+                }
+            };
+            entries.lock().insert(key, entry);
+            ctx.request_repaint();
+        });
+        Entry::Pending
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_file(path: &str) -> Entry {
+    let path = path.strip_prefix("file://").unwrap_or(path);
+    match std::fs::read(path) {
+        Ok(bytes) => Entry::Ready(bytes.into()),
+        Err(err) => {
+            log::warn!("Failed to read image {path}: {err}");
+            Entry::Failed
+        }
+    }
+}
+
+/// There is no file system to read from on the web.
+#[cfg(target_arch = "wasm32")]
+fn read_file(_path: &str) -> Entry {
+    Entry::Failed
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn disk_cache_dir() -> Option<std::path::PathBuf> {
+    eframe::storage_dir(crate::APP_NAME).map(|dir| dir.join("image_cache"))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn disk_cache_path(key: u64) -> Option<std::path::PathBuf> {
+    disk_cache_dir().map(|dir| dir.join(format!("{key:016x}")))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_disk_cache(key: u64, bytes: &[u8]) {
+    let Some(path) = disk_cache_path(key) else {
+        return;
+    };
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| std::fs::write(&path, bytes));
+    if let Err(err) = result {
+        log::warn!("Failed to cache image at {}: {err}", path.display());
+    }
+}
+
+/// Whether `text` refers to an image (a data URI, or a URL or path with an image extension)
+/// rather than being plain text.
+pub fn is_image_ref(text: &str) -> bool {
+    // The formats enabled for the `image` crate in `Cargo.toml`.
+    const EXTENSIONS: [&str; 3] = ["png", "jpg", "jpeg"];
+    let text = text.trim();
+    if text.starts_with("data:image/") {
+        return true;
+    }
+    if text.contains(char::is_whitespace) {
+        return false;
+    }
+    let path = text.split(['?', '#']).next().unwrap_or(text);
+    path.rsplit_once('.').is_some_and(|(_, ext)| {
+        EXTENSIONS
+            .iter()
+            .any(|image_ext| ext.eq_ignore_ascii_case(image_ext))
+    })
+}
+
+/// Decodes a `data:image/...;base64,...` URI into the raw image bytes.
+///
+/// Returns `None` if `uri` is not a base64 data URI or the payload is malformed.
+pub fn decode_data_uri(uri: &str) -> Option<Vec<u8>> {
+    let (header, payload) = uri.strip_prefix("data:")?.split_once(',')?;
+    if !header.ends_with(";base64") {
+        return None;
+    }
+    // Long data URIs are often wrapped across lines when pasted into a JSON file.
+    let payload: String = payload.split_ascii_whitespace().collect();
+    base64::engine::general_purpose::STANDARD
+        .decode(payload)
+        .ok()
+}
+
+/// The image with the given [`crate::stable_hash`] of its source, under the same URI wherever
+/// it is shown so egui loads its texture only once.
+fn image(key: u64, bytes: Arc<[u8]>) -> egui::Image<'static> {
+    egui::Image::from_bytes(format!("bytes://quiz-image-{key:016x}"), bytes)
+}
@@ -0,0 +1,481 @@
+use crate::quiz_core::Question;
+use serde::{Deserialize, Serialize};
+
+/// The JSON schema of a quiz file, used when [`ImportOptions::validate_schema`] is set.
+pub const QUIZ_SCHEMA: &str = include_str!("../assets/quiz.schema.json");
+
+/// How text fields are cleaned up when a quiz is loaded.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImportOptions {
+    /// Trim every field and collapse runs of whitespace on each line into a single space.
+    /// Line breaks are kept. The "Zeilenumbrüche zusammenfassen" view setting decides whether
+    /// they show.
+    pub normalize_whitespace: bool,
+    /// Check the file against [`QUIZ_SCHEMA`] first, for precise error messages.
+    pub validate_schema: bool,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            normalize_whitespace: true,
+            validate_schema: false,
+        }
+    }
+}
+
+impl ImportOptions {
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(
+            &mut self.normalize_whitespace,
+            "Leerzeichen beim Import bereinigen",
+        );
+        ui.checkbox(&mut self.validate_schema, "Streng prüfen (JSON-Schema)");
+    }
+}
+
+/// The formats a quiz can be loaded from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuizFormat {
+    Json,
+    Xlsx,
+    /// Tab-separated values, one question per line.
+    Tsv,
+    /// Comma-separated values, one question per line.
+    Csv,
+    /// A numbered list of questions with their answers, see [`load_questions_from_list`].
+    NumberedList,
+}
+
+impl QuizFormat {
+    pub fn name(self) -> &'static str {
+        match self {
+            QuizFormat::Json => "JSON",
+            QuizFormat::Xlsx => "Excel",
+            QuizFormat::Tsv => "TSV",
+            QuizFormat::Csv => "CSV",
+            QuizFormat::NumberedList => "nummerierte Liste",
+        }
+    }
+
+    /// Whether `name` has the usual extension of this format.
+    pub fn matches_extension(self, name: &str) -> bool {
+        let extension = name.rsplit_once('.').map_or("", |(_, ext)| ext);
+        extension.eq_ignore_ascii_case(match self {
+            QuizFormat::Json => "json",
+            QuizFormat::Xlsx => "xlsx",
+            QuizFormat::Tsv => "tsv",
+            QuizFormat::Csv => "csv",
+            QuizFormat::NumberedList => "txt",
+        })
+    }
+
+    /// Guesses the format from the content, falling back to the extension of `name` when the
+    /// content could be either, e.g. a CSV file whose questions contain tabs.
+    ///
+    /// A numbered list is recognized by any of its first few non-empty lines, so it may start
+    /// with a title, but it also needs an answer line. A `.csv` or `.tsv` file is never taken
+    /// as one, even if its questions are numbered.
+    pub fn detect(name: &str, bytes: &[u8]) -> Self {
+        let extension = name
+            .rsplit_once('.')
+            .map(|(_, ext)| ext.to_ascii_lowercase())
+            .unwrap_or_default();
+        // An `.xlsx` file is a zip archive.
+        if bytes.starts_with(b"PK\x03\x04") {
+            return QuizFormat::Xlsx;
+        }
+        let text = String::from_utf8_lossy(bytes);
+        let text = text.trim_start_matches('\u{feff}').trim_start();
+        if text.starts_with('[') || text.starts_with('{') {
+            return QuizFormat::Json;
+        }
+        let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+        let has_item = lines
+            .clone()
+            .take(3)
+            .any(|line| list_item(line).is_some() && !line.contains('\t'));
+        let has_answer = text
+            .lines()
+            .any(|line| strip_label(line.trim(), &["Antwort:", "Answer:"]).is_some());
+        if has_item && has_answer && !matches!(extension.as_str(), "csv" | "tsv") {
+            return QuizFormat::NumberedList;
+        }
+        let first_line = lines.next().unwrap_or_default();
+        match (first_line.contains('\t'), first_line.contains(',')) {
+            (true, true) if extension == "csv" => QuizFormat::Csv,
+            (true, _) => QuizFormat::Tsv,
+            (false, true) => QuizFormat::Csv,
+            (false, false) => match extension.as_str() {
+                "tsv" => QuizFormat::Tsv,
+                "csv" => QuizFormat::Csv,
+                "xlsx" => QuizFormat::Xlsx,
+                _ => QuizFormat::Json,
+            },
+        }
+    }
+}
+
+/// Parses a quiz from the raw bytes of a file, describing what went wrong if it can't.
+///
+/// The format is taken from [`QuizFormat::detect`] and returned along with the questions. Every
+/// question must pass [`Question::validate`], whatever the format.
+pub fn load_questions(
+    name: &str,
+    bytes: &[u8],
+    options: &ImportOptions,
+) -> Result<(Vec<Question>, QuizFormat), String> {
+    let format = QuizFormat::detect(name, bytes);
+    let questions = match format {
+        QuizFormat::Xlsx => load_questions_from_xlsx(bytes, options)?,
+        QuizFormat::Json | QuizFormat::Tsv | QuizFormat::Csv | QuizFormat::NumberedList => {
+            let text = std::str::from_utf8(bytes).map_err(|_| "keine gültige UTF-8-Textdatei")?;
+            let text = text.trim_start_matches('\u{feff}');
+            match format {
+                QuizFormat::Tsv => load_questions_from_delimited(text, '\t', options)?,
+                QuizFormat::Csv => load_questions_from_delimited(text, ',', options)?,
+                QuizFormat::NumberedList => load_questions_from_list(text, options)?,
+                _ => {
+                    if options.validate_schema {
+                        validate_schema(text)?;
+                    }
+                    load_questions_from_str(text, options).map_err(|err| err.to_string())?
+                }
+            }
+        }
+    };
+    if questions.is_empty() {
+        return Err("die Datei enthält keine Fragen".to_owned());
+    }
+    for (nr, question) in questions.iter().enumerate() {
+        if let Err(errors) = question.validate() {
+            let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+            return Err(format!("Frage {}: {}", nr + 1, errors.join(", ")));
+        }
+    }
+    Ok((questions, format))
+}
+
+/// A quiz file that wraps the questions in an object, so that it can carry other keys such as
+/// `"_comment"`. They are ignored.
+#[derive(Deserialize)]
+struct WrappedQuiz {
+    questions: Vec<Question>,
+}
+
+/// Parses a quiz from its JSON text and cleans it up according to `options`.
+///
+/// The text is either an array of questions or an object with them under `"questions"`.
+pub fn load_questions_from_str(
+    text: &str,
+    options: &ImportOptions,
+) -> serde_json::Result<Vec<Question>> {
+    let mut questions = match text.trim_start().starts_with('{') {
+        true => serde_json::from_str::<WrappedQuiz>(text)?.questions,
+        false => serde_json::from_str(text)?,
+    };
+    clean_up(&mut questions, options);
+    Ok(questions)
+}
+
+/// Reads a quiz from the first sheet of an Excel workbook, see [`questions_from_rows`].
+pub fn load_questions_from_xlsx(
+    bytes: &[u8],
+    options: &ImportOptions,
+) -> Result<Vec<Question>, String> {
+    use calamine::Reader as _;
+
+    let mut workbook = calamine::Xlsx::new(std::io::Cursor::new(bytes))
+        .map_err(|err| format!("keine gültige Excel-Datei: {err}"))?;
+    let range = workbook
+        .worksheet_range_at(0)
+        .ok_or("die Excel-Datei enthält kein Tabellenblatt")?
+        .map_err(|err| err.to_string())?;
+
+    // Leading empty rows are not part of the range.
+    let first_row = range.start().map_or(0, |(row, _)| row as usize);
+    let rows = range
+        .rows()
+        .map(|row| row.iter().map(|cell| cell.to_string()).collect());
+    questions_from_rows(rows, first_row, options)
+}
+
+/// Reads a quiz from lines of values separated by `delimiter`, see [`questions_from_rows`].
+///
+/// Values can be quoted with `"` to contain the delimiter, line breaks or `""` for a quote.
+pub fn load_questions_from_delimited(
+    text: &str,
+    delimiter: char,
+    options: &ImportOptions,
+) -> Result<Vec<Question>, String> {
+    questions_from_rows(split_delimited(text, delimiter).into_iter(), 0, options)
+}
+
+/// Reads a quiz written as a numbered list, like
+///
+/// ```text
+/// 1. Wie heißt die Hauptstadt von Frankreich?
+/// Hinweis: Sie liegt an der Seine.
+/// Antwort: Paris
+/// ```
+///
+/// A line starting with a number and a period begins a new question. A line starting with
+/// "Antwort:" or "Answer:" holds the answer, and up to two lines starting with "Hinweis:" or
+/// "Hint:" hold the hints. All other lines are part of the question. Text before the first
+/// question, like a title, is skipped. Anything else that doesn't fit, like a question with no
+/// or two answers, is an error.
+pub fn load_questions_from_list(
+    text: &str,
+    options: &ImportOptions,
+) -> Result<Vec<Question>, String> {
+    let mut questions: Vec<(usize, Question, Option<String>)> = Vec::new();
+    for (nr, line) in text.lines().enumerate() {
+        let line = line.trim();
+        let at_line = || format!("Zeile {}", nr + 1);
+        if let Some(question) = list_item(line) {
+            questions.push((nr, Question::new(question, String::new()), None));
+            continue;
+        }
+        let Some((_, question, answer)) = questions.last_mut() else {
+            continue;
+        };
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(text) = strip_label(line, &["Antwort:", "Answer:"]) {
+            if answer.replace(text.to_owned()).is_some() {
+                return Err(format!("{}: zweite Antwort zur selben Frage", at_line()));
+            }
+        } else if let Some(text) = strip_label(line, &["Hinweis:", "Hint:"]) {
+            match (question.hint1.is_empty(), question.hint2.is_empty()) {
+                (true, _) => question.hint1 = text.to_owned(),
+                (false, true) => question.hint2 = text.to_owned(),
+                (false, false) => return Err(format!("{}: mehr als zwei Hinweise", at_line())),
+            }
+        } else if answer.is_some() {
+            return Err(format!("{}: Text nach der Antwort", at_line()));
+        } else {
+            question.question.push('\n');
+            question.question += line;
+        }
+    }
+    let mut questions = questions
+        .into_iter()
+        .map(|(nr, question, answer)| match answer {
+            Some(answer) => Ok(Question { answer, ..question }),
+            None => Err(format!("Zeile {}: Frage ohne Antwort", nr + 1)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    clean_up(&mut questions, options);
+    Ok(questions)
+}
+
+/// The text of a line like `12. Question`, if it is one.
+fn list_item(line: &str) -> Option<&str> {
+    let (number, text) = line.trim_start().split_once('.')?;
+    let is_number = !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit());
+    // Not a decimal number like "3.14".
+    let is_item = text.is_empty() || text.starts_with(char::is_whitespace);
+    (is_number && is_item).then(|| text.trim())
+}
+
+/// The rest of `line` after one of `labels`, ignoring case.
+fn strip_label<'a>(line: &'a str, labels: &[&str]) -> Option<&'a str> {
+    labels.iter().find_map(|label| {
+        let prefix = line.get(..label.len())?;
+        prefix
+            .eq_ignore_ascii_case(label)
+            .then(|| line[label.len()..].trim())
+    })
+}
+
+/// Turns table rows into questions.
+///
+/// The first four columns are the question, the two hints and the answer. A first row
+/// starting with "Frage" or "question" is taken as a header and skipped, as are empty rows.
+/// Rows are numbered from `first_row` in error messages.
+fn questions_from_rows(
+    rows: impl Iterator<Item = Vec<String>>,
+    first_row: usize,
+    options: &ImportOptions,
+) -> Result<Vec<Question>, String> {
+    let mut questions = Vec::new();
+    for (nr, cells) in rows.enumerate() {
+        let is_header = nr == 0
+            && cells.first().is_some_and(|cell| {
+                let cell = cell.trim();
+                cell.eq_ignore_ascii_case("frage") || cell.eq_ignore_ascii_case("question")
+            });
+        if is_header || cells.iter().all(|cell| cell.trim().is_empty()) {
+            continue;
+        }
+        let mut fields = cells.into_iter();
+        let mut next = || fields.next().unwrap_or_default();
+        let (question, hint1, hint2, answer) = (next(), next(), next(), next());
+        let question = Question {
+            hint1,
+            hint2,
+            ..Question::new(question, answer)
+        };
+        if let Err(errors) = question.validate() {
+            let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+            return Err(format!(
+                "Zeile {}: {} (erwartet werden die Spalten Frage, Hinweis 1, Hinweis 2, Antwort)",
+                first_row + nr + 1,
+                errors.join(", ")
+            ));
+        }
+        questions.push(question);
+    }
+    clean_up(&mut questions, options);
+    Ok(questions)
+}
+
+/// Splits `text` into rows of values, honoring quotes like a spreadsheet program's CSV export.
+fn split_delimited(text: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut value = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                value.push('"');
+            }
+            (true, '"') => quoted = false,
+            (true, c) => value.push(c),
+            (false, '"') if value.is_empty() => quoted = true,
+            (false, c) if c == delimiter => row.push(std::mem::take(&mut value)),
+            (false, '\r') => {}
+            (false, '\n') => {
+                row.push(std::mem::take(&mut value));
+                rows.push(std::mem::take(&mut row));
+            }
+            (false, c) => value.push(c),
+        }
+    }
+    if !value.is_empty() || !row.is_empty() {
+        row.push(value);
+        rows.push(row);
+    }
+    rows
+}
+
+fn clean_up(questions: &mut [Question], options: &ImportOptions) {
+    if options.normalize_whitespace {
+        for question in questions {
+            let fields = [
+                &mut question.question,
+                &mut question.hint1,
+                &mut question.hint2,
+                &mut question.answer,
+            ];
+            let alternatives = question.alternatives.iter_mut();
+            for field in fields
+                .into_iter()
+                .chain(alternatives)
+                .chain(&mut question.source)
+            {
+                *field = normalize_whitespace(field, true);
+            }
+            for tag in &mut question.tags {
+                *tag = normalize_whitespace(tag, false);
+            }
+        }
+    }
+}
+
+/// Checks `text` against [`QUIZ_SCHEMA`], listing every problem like
+/// `questions[4].answer: fehlt`.
+fn validate_schema(text: &str) -> Result<(), String> {
+    let schema = serde_json::from_str(QUIZ_SCHEMA).expect("the embedded schema is valid JSON");
+    let validator = jsonschema::validator_for(&schema).expect("the embedded schema is valid");
+    let mut instance: serde_json::Value =
+        serde_json::from_str(text).map_err(|err| err.to_string())?;
+    // Only the questions of a wrapped quiz are described by the schema.
+    if let Some(questions) = instance.get_mut("questions") {
+        instance = questions.take();
+    }
+
+    let errors: Vec<String> = validator
+        .iter_errors(&instance)
+        .map(|err| {
+            let mut path = String::from("questions");
+            for segment in err.instance_path.as_str().split('/').skip(1) {
+                match segment.parse::<usize>() {
+                    Ok(index) => path += &format!("[{index}]"),
+                    Err(_) => path += &format!(".{segment}"),
+                }
+            }
+            match &err.kind {
+                jsonschema::error::ValidationErrorKind::Required { property } => {
+                    let property = property.as_str().unwrap_or_default();
+                    format!("{path}.{property}: fehlt")
+                }
+                _ => format!("{path}: {err}"),
+            }
+        })
+        .collect();
+    match errors.is_empty() {
+        true => Ok(()),
+        false => Err(errors.join("\n")),
+    }
+}
+
+/// Trims `text` and collapses internal runs of whitespace into a single space.
+///
+/// With `keep_newlines`, every line is collapsed on its own and the line breaks are kept.
+fn normalize_whitespace(text: &str, keep_newlines: bool) -> String {
+    let collapse = |line: &str| line.split_whitespace().collect::<Vec<_>>().join(" ");
+    match keep_newlines {
+        true => text
+            .trim()
+            .lines()
+            .map(collapse)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        false => collapse(text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbered_list_may_start_with_a_title() {
+        let text = "Hauptstädte, Teil 1\n\n1. Hauptstadt von Frankreich?\nAntwort: Paris\n";
+        let (questions, format) =
+            load_questions("quiz.txt", text.as_bytes(), &ImportOptions::default()).unwrap();
+        assert_eq!(format, QuizFormat::NumberedList);
+        assert_eq!(questions.len(), 1);
+        assert_eq!(questions[0].question, "Hauptstadt von Frankreich?");
+        assert_eq!(questions[0].answer, "Paris");
+    }
+
+    #[test]
+    fn alternatives_and_source_are_normalized() {
+        let text = r#"[{"question": "Q", "answer": ["  A  ", "  B   c  "], "source": " S  1 "}]"#;
+        let (questions, _) =
+            load_questions("quiz.json", text.as_bytes(), &ImportOptions::default()).unwrap();
+        assert_eq!(questions[0].answer, "A");
+        assert_eq!(questions[0].alternatives, ["B c"]);
+        assert_eq!(questions[0].source.as_deref(), Some("S 1"));
+    }
+
+    #[test]
+    fn csv_with_numbered_questions_stays_csv() {
+        let text = "Frage,Hinweis 1,Hinweis 2,Antwort\n\
+                    1. Hauptstadt von Frankreich?,Seine,Eiffelturm,Paris\n";
+        for name in ["quiz.csv", "quiz.txt"] {
+            let (questions, format) =
+                load_questions(name, text.as_bytes(), &ImportOptions::default()).unwrap();
+            assert_eq!(format, QuizFormat::Csv);
+            assert_eq!(questions.len(), 1);
+            assert_eq!(questions[0].answer, "Paris");
+        }
+    }
+}
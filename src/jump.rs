@@ -0,0 +1,74 @@
+use crate::quiz_core::Question;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher as _;
+
+/// How many matches the dropdown lists.
+const MAX_RESULTS: usize = 8;
+
+/// A search box that jumps to a question by its text, forgiving typos and missing letters.
+#[derive(Default)]
+pub struct JumpSearch {
+    query: String,
+}
+
+impl JumpSearch {
+    /// Returns the index of the question to jump to, if one was picked.
+    pub fn ui(&mut self, ui: &mut egui::Ui, questions: &[Question]) -> Option<usize> {
+        let response = ui.add(
+            egui::TextEdit::singleline(&mut self.query)
+                .hint_text("🔍 Frage suchen")
+                .desired_width(160.0),
+        );
+        let results = self.results(questions);
+        let popup_id = response.id.with("results");
+        if response.has_focus() && !results.is_empty() {
+            ui.memory_mut(|memory| memory.open_popup(popup_id));
+        }
+
+        let mut picked = None;
+        if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+            picked = results.first().copied();
+        }
+        egui::popup_below_widget(
+            ui,
+            popup_id,
+            &response,
+            egui::PopupCloseBehavior::CloseOnClickOutside,
+            |ui| {
+                ui.set_min_width(320.0);
+                for &nr in &results {
+                    let preview = format!("{nr}. {}", questions[nr].question);
+                    if ui.selectable_label(false, preview).clicked() {
+                        picked = Some(nr);
+                    }
+                }
+            },
+        );
+        if picked.is_some() {
+            self.query.clear();
+            ui.memory_mut(|memory| memory.close_popup());
+        }
+        picked
+    }
+
+    /// The questions best matching the query, best first.
+    fn results(&self, questions: &[Question]) -> Vec<usize> {
+        let query = self.query.trim();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let matcher = SkimMatcherV2::default().ignore_case();
+        let mut scored: Vec<(i64, usize)> = questions
+            .iter()
+            .enumerate()
+            .filter_map(|(nr, q)| Some((matcher.fuzzy_match(&q.question, query)?, nr)))
+            .collect();
+        // Ties keep the order of the quiz.
+        scored.sort_by_key(|&(score, nr)| (std::cmp::Reverse(score), nr));
+        scored
+            .into_iter()
+            .take(MAX_RESULTS)
+            .map(|(_, nr)| nr)
+            .collect()
+    }
+}
@@ -0,0 +1,47 @@
+use egui::Key;
+use serde::{Deserialize, Serialize};
+
+/// Single keys for the most common actions. `None` turns an action's key off.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBindings {
+    /// Reveals or hides the answer.
+    pub toggle_answer: Option<Key>,
+    /// Shows the answer for as long as the key is held, without revealing it for good.
+    pub peek_answer: Option<Key>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            toggle_answer: Some(Key::A),
+            peek_answer: Some(Key::P),
+        }
+    }
+}
+
+impl KeyBindings {
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("key_bindings_grid").show(ui, |ui| {
+            key_choice(ui, "Antwort aufdecken/verbergen:", &mut self.toggle_answer);
+            key_choice(
+                ui,
+                "Antwort zeigen, solange gedrückt:",
+                &mut self.peek_answer,
+            );
+        });
+    }
+}
+
+fn key_choice(ui: &mut egui::Ui, label: &str, key: &mut Option<Key>) {
+    ui.label(label);
+    egui::ComboBox::from_id_salt(label)
+        .selected_text(key.map_or("aus", Key::name))
+        .show_ui(ui, |ui| {
+            ui.selectable_value(key, None, "aus");
+            for &choice in Key::ALL {
+                ui.selectable_value(key, Some(choice), choice.name());
+            }
+        });
+    ui.end_row();
+}
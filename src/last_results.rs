@@ -0,0 +1,32 @@
+use crate::quiz_core::Question;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Whether each question was answered correctly the last time it was part of an exam, across
+/// sessions.
+///
+/// Keyed like [`crate::reveal_counts::RevealCounts`]: per quiz and per hash of the question.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LastResults {
+    quizzes: HashMap<u64, HashMap<u64, bool>>,
+}
+
+impl LastResults {
+    /// Whether there is a result for any question of the quiz.
+    pub fn has_quiz(&self, quiz: u64) -> bool {
+        self.quizzes.contains_key(&quiz)
+    }
+
+    pub fn get(&self, quiz: u64, question: &Question) -> Option<bool> {
+        self.quizzes
+            .get(&quiz)
+            .and_then(|results| results.get(&crate::stable_hash(&question.question)))
+            .copied()
+    }
+
+    pub fn record(&mut self, quiz: u64, question: &Question, correct: bool) {
+        let key = crate::stable_hash(&question.question);
+        self.quizzes.entry(quiz).or_default().insert(key, correct);
+    }
+}
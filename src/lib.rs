@@ -1,4 +1,40 @@
 #![warn(clippy::all, rust_2018_idioms)]
 
+mod answer_sheet;
 mod app;
+mod diff;
+mod edit;
+mod exam;
+mod export;
+mod find_replace;
+mod flashcard;
+mod images;
+mod import;
+mod jump;
+mod keys;
+mod last_results;
+mod panic;
+mod playlist;
+mod qr;
+mod quiz_core;
+mod reveal_counts;
+mod screenshot;
+mod session;
+mod shuffle;
+mod slideshow;
+mod stats;
+mod tags;
+mod toasts;
 pub use app::MyApp;
+pub use quiz_core::{FieldError, Question, Quiz, Section, Show, TagFilter, TagMatch};
+
+/// Window title, also used to locate the app's storage directory.
+pub const APP_NAME: &str = "Duell um die Welt";
+
+/// FNV-1a, for hashes that are persisted or must look the same in every session, because
+/// `DefaultHasher` makes no promise to stay the same across Rust releases.
+pub(crate) fn stable_hash(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
@@ -18,7 +18,7 @@ fn main() -> eframe::Result {
         ..Default::default()
     };
     eframe::run_native(
-        "Duell um die Welt",
+        quiz::APP_NAME,
         native_options,
         Box::new(|cc| Ok(Box::new(quiz::MyApp::new(cc)))),
     )
@@ -0,0 +1,45 @@
+use std::sync::Mutex;
+
+/// The message of the last panic, until it is shown to the user.
+static PANIC_MESSAGE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Remembers panic messages so they can be shown on the error screen.
+///
+/// Natively, [`crate::MyApp`] catches the panic and shows the message itself. On wasm, panics
+/// abort, so the hook replaces the page with the message and a button to reset the stored state.
+pub fn install_hook() {
+    let previous_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = info.to_string();
+        #[cfg(target_arch = "wasm32")]
+        show_in_page(&message);
+        if let Ok(mut last) = PANIC_MESSAGE.lock() {
+            *last = Some(message);
+        }
+        previous_hook(info);
+    }));
+}
+
+/// The message of the last panic, if it hasn't been taken yet.
+pub fn take_message() -> Option<String> {
+    PANIC_MESSAGE.lock().ok().and_then(|mut last| last.take())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn show_in_page(message: &str) {
+    let escaped = message
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+    let body = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.body());
+    if let Some(body) = body {
+        body.set_inner_html(&format!(
+            "<div style=\"padding: 1em; font-family: sans-serif; color: #ddd\">\
+             <h2>Ein Fehler ist aufgetreten</h2><pre>{escaped}</pre>\
+             <button onclick=\"localStorage.clear(); location.reload()\">\
+             Zustand zurücksetzen</button></div>"
+        ));
+    }
+}
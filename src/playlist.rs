@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// A quiz file queued in the [`Playlist`].
+#[derive(Serialize, Deserialize)]
+pub struct PlaylistEntry {
+    pub name: String,
+    /// Where the file lives on disk. Always `None` on wasm.
+    pub path: Option<PathBuf>,
+    /// The file contents as read when the entry was added.
+    ///
+    /// Not persisted, so after a restart only entries with a `path` can still be played.
+    #[serde(skip)]
+    pub text: Option<String>,
+}
+
+impl PlaylistEntry {
+    /// The current contents of the file, preferring a fresh read from disk.
+    pub fn read(&self) -> Option<String> {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(text) = self
+            .path
+            .as_ref()
+            .and_then(|p| std::fs::read_to_string(p).ok())
+        {
+            return Some(text);
+        }
+        self.text.clone()
+    }
+}
+
+/// What the user asked for in [`Playlist::ui`].
+pub enum PlaylistAction {
+    AddFiles,
+    Play(usize),
+}
+
+/// Several quiz files played one after another.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Playlist {
+    pub entries: Vec<PlaylistEntry>,
+    /// The entry whose quiz is currently loaded.
+    pub current: Option<usize>,
+}
+
+impl Playlist {
+    /// Drops entries that can no longer be read after a restart.
+    pub fn retain_readable(&mut self) {
+        self.entries.retain(|entry| entry.path.is_some());
+        if self.current.is_some_and(|i| i >= self.entries.len()) {
+            self.current = None;
+        }
+    }
+
+    /// The entry following the current one, if any.
+    pub fn next(&self) -> Option<usize> {
+        self.current
+            .map(|i| i + 1)
+            .filter(|&i| i < self.entries.len())
+    }
+
+    /// "Datei 2/3" while a playlist entry is loaded.
+    pub fn position_label(&self) -> Option<String> {
+        self.current
+            .map(|i| format!("Datei {}/{}", i + 1, self.entries.len()))
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) -> Option<PlaylistAction> {
+        let mut action = None;
+        let mut remove = None;
+        for (i, entry) in self.entries.iter().enumerate() {
+            ui.horizontal(|ui| {
+                if ui.small_button("✖").clicked() {
+                    remove = Some(i);
+                }
+                let text = format!("{}. {}", i + 1, entry.name);
+                if ui.selectable_label(self.current == Some(i), text).clicked() {
+                    action = Some(PlaylistAction::Play(i));
+                }
+            });
+        }
+        if let Some(i) = remove {
+            self.entries.remove(i);
+            self.current = match self.current {
+                Some(current) if current == i => None,
+                Some(current) if current > i => Some(current - 1),
+                current => current,
+            };
+        }
+
+        if !self.entries.is_empty() {
+            ui.separator();
+        }
+        if ui.button("Dateien hinzufügen").clicked() {
+            action = Some(PlaylistAction::AddFiles);
+        }
+        action
+    }
+}
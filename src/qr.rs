@@ -0,0 +1,36 @@
+use egui::{Color32, Rect, Sense, Vec2};
+use qrcode::{Color, QrCode};
+
+/// Modules of light border around the code, as scanners expect.
+const QUIET_ZONE: usize = 4;
+
+/// Draws `text` as a QR code, black on white regardless of the theme so phones can scan it
+/// from a projector.
+pub fn qr_code_ui(ui: &mut egui::Ui, text: &str) {
+    let code = match QrCode::new(text) {
+        Ok(code) => code,
+        Err(err) => {
+            ui.colored_label(ui.visuals().warn_fg_color, format!("⚠ {err}"));
+            return;
+        }
+    };
+    let modules = code.width() + 2 * QUIET_ZONE;
+    // Whole points per module keep the edges sharp at the usual zoom levels.
+    let module_size = (400.0 / modules as f32).floor().max(4.0);
+    let (rect, _) =
+        ui.allocate_exact_size(Vec2::splat(module_size * modules as f32), Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0.0, Color32::WHITE);
+    for (i, color) in code.to_colors().into_iter().enumerate() {
+        if color == Color::Dark {
+            let x = (i % code.width() + QUIET_ZONE) as f32;
+            let y = (i / code.width() + QUIET_ZONE) as f32;
+            let min = rect.min + Vec2::new(x, y) * module_size;
+            painter.rect_filled(
+                Rect::from_min_size(min, Vec2::splat(module_size)),
+                0.0,
+                Color32::BLACK,
+            );
+        }
+    }
+}
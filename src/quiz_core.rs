@@ -0,0 +1,426 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(from = "QuestionFile", into = "QuestionFile")]
+pub struct Question {
+    pub question: String,
+    pub hint1: String,
+    pub hint2: String,
+    pub answer: String,
+    /// Further answers that count as correct. In the quiz file they follow `answer` in a list.
+    pub alternatives: Vec<String>,
+    /// Optional image shown below the question, given as an `http(s)` URL, a file path or a
+    /// `data:image/...;base64,...` URI. Hints can be images in the same way.
+    pub image: Option<String>,
+    pub tags: Vec<String>,
+    /// Where the question comes from, e.g. a textbook.
+    pub source: Option<String>,
+    /// Seconds to answer the question, after which the answer is revealed.
+    pub time_limit: Option<u32>,
+}
+
+/// A [`Question`] as written in a quiz file, where `answer` is a string or a list.
+#[derive(Serialize, Deserialize)]
+struct QuestionFile {
+    question: String,
+    #[serde(default)]
+    hint1: String,
+    #[serde(default)]
+    hint2: String,
+    answer: AnswerField,
+    #[serde(default)]
+    image: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    source: Option<String>,
+    #[serde(default)]
+    time_limit: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum AnswerField {
+    One(String),
+    /// The answer followed by its alternatives.
+    Alternatives(Vec<String>),
+}
+
+impl From<QuestionFile> for Question {
+    fn from(file: QuestionFile) -> Self {
+        let (answer, alternatives) = match file.answer {
+            AnswerField::One(answer) => (answer, Vec::new()),
+            AnswerField::Alternatives(mut answers) => match answers.is_empty() {
+                true => (String::new(), answers),
+                false => (answers.remove(0), answers),
+            },
+        };
+        Self {
+            question: file.question,
+            hint1: file.hint1,
+            hint2: file.hint2,
+            answer,
+            alternatives,
+            image: file.image,
+            tags: file.tags,
+            source: file.source,
+            time_limit: file.time_limit,
+        }
+    }
+}
+
+impl From<Question> for QuestionFile {
+    fn from(question: Question) -> Self {
+        let answer = match question.alternatives.is_empty() {
+            true => AnswerField::One(question.answer),
+            false => {
+                let mut answers = question.alternatives;
+                answers.insert(0, question.answer);
+                AnswerField::Alternatives(answers)
+            }
+        };
+        Self {
+            question: question.question,
+            hint1: question.hint1,
+            hint2: question.hint2,
+            answer,
+            image: question.image,
+            tags: question.tags,
+            source: question.source,
+            time_limit: question.time_limit,
+        }
+    }
+}
+
+/// A required field of a [`Question`] that is empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldError {
+    /// The name of the field as in the quiz file, e.g. `"hint1"`.
+    pub field: &'static str,
+}
+
+impl std::fmt::Display for FieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: fehlt", self.field)
+    }
+}
+
+impl Question {
+    /// A question without hints, tags or any of the optional fields.
+    pub fn new(question: impl Into<String>, answer: impl Into<String>) -> Self {
+        Self {
+            question: question.into(),
+            hint1: String::new(),
+            hint2: String::new(),
+            answer: answer.into(),
+            alternatives: Vec::new(),
+            image: None,
+            tags: Vec::new(),
+            source: None,
+            time_limit: None,
+        }
+    }
+
+    /// Fills the first hint that is still empty. A third hint replaces the second one.
+    pub fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        match self.hint1.is_empty() {
+            true => self.hint1 = hint.into(),
+            false => self.hint2 = hint.into(),
+        }
+        self
+    }
+
+    /// Adds a tag, which works as the question's category in the tag filter.
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// The answer and its alternatives.
+    pub fn answers(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.answer.as_str()).chain(self.alternatives.iter().map(String::as_str))
+    }
+
+    /// All accepted answers for display, separated by slashes.
+    pub fn answers_text(&self) -> String {
+        self.answers().collect::<Vec<_>>().join(" / ")
+    }
+
+    /// Whether `response` matches any accepted answer, ignoring case and surrounding whitespace.
+    pub fn accepts(&self, response: &str) -> bool {
+        let response = response.trim().to_lowercase();
+        !response.is_empty()
+            && self
+                .answers()
+                .any(|answer| answer.trim().to_lowercase() == response)
+    }
+
+    /// Checks that the question and the answer are filled in. Hints are optional.
+    pub fn validate(&self) -> Result<(), Vec<FieldError>> {
+        let errors: Vec<FieldError> = [("question", &self.question), ("answer", &self.answer)]
+            .into_iter()
+            .filter(|(_, text)| text.trim().is_empty())
+            .map(|(field, _)| FieldError { field })
+            .collect();
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(errors),
+        }
+    }
+}
+
+/// Which sections of the current question are revealed.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Show {
+    pub question: bool,
+    pub hint1: bool,
+    pub hint2: bool,
+    pub answer: bool,
+    /// How many letters of each word of the answer are revealed while `answer` is hidden.
+    #[serde(default)]
+    pub answer_letters: usize,
+}
+
+/// A part of a question that can be revealed on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    Question,
+    Hint1,
+    Hint2,
+    Answer,
+}
+
+impl Show {
+    pub fn reveal(&mut self, section: Section) {
+        match section {
+            Section::Question => self.question = true,
+            Section::Hint1 => self.hint1 = true,
+            Section::Hint2 => self.hint2 = true,
+            Section::Answer => self.answer = true,
+        }
+    }
+
+    /// Reveals one more letter of each word of `answer`, and the whole answer once the
+    /// longest word is complete.
+    pub fn reveal_letter(&mut self, answer: &str) {
+        self.answer_letters += 1;
+        let longest_word = answer.split_whitespace().map(|w| w.chars().count()).max();
+        if self.answer_letters >= longest_word.unwrap_or(0) {
+            self.answer = true;
+        }
+    }
+}
+
+/// How the selected tags of a [`TagFilter`] are combined.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TagMatch {
+    /// A question matches if it has at least one of the selected tags.
+    #[default]
+    Any,
+    /// A question matches only if it has every selected tag.
+    All,
+}
+
+/// Restricts navigation to questions carrying the selected tags.
+///
+/// Tags can form a hierarchy like `"Biologie / Zellbiologie"`, and selecting `"Biologie"`
+/// also matches every tag below it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TagFilter {
+    pub selected: BTreeSet<String>,
+    pub mode: TagMatch,
+}
+
+impl TagFilter {
+    /// An empty selection matches every question.
+    pub fn matches(&self, tags: &[String]) -> bool {
+        if self.selected.is_empty() {
+            return true;
+        }
+        let has = |selected: &String| tags.iter().any(|tag| is_within(tag, selected));
+        match self.mode {
+            TagMatch::Any => self.selected.iter().any(has),
+            TagMatch::All => self.selected.iter().all(has),
+        }
+    }
+}
+
+/// Whether `tag` is `parent` or lies below it in the hierarchy of `/`-separated segments.
+fn is_within(tag: &str, parent: &str) -> bool {
+    let mut segments = tag.split('/').map(str::trim);
+    parent
+        .split('/')
+        .map(str::trim)
+        .all(|segment| segments.next() == Some(segment))
+}
+
+/// A quiz without its GUI: the questions, which one is shown, what of it is revealed and
+/// which answers were right. It can be driven from another program as well.
+///
+/// [`MyApp`](crate::MyApp) keeps its quiz in one of these and adds everything that only makes
+/// sense on screen, like the slideshow, playlists and remembering reveals per question. It
+/// also decides on its own what is revealed of a question it moves to. No questions means that
+/// no quiz is loaded.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Quiz {
+    pub questions: Vec<Question>,
+    pub question_nr: usize,
+    /// What is revealed of the current question. [`Self::next_question`] and
+    /// [`Self::prev_question`] hide everything again.
+    pub show: Show,
+    /// Navigating skips questions that don't pass the filter.
+    pub tag_filter: TagFilter,
+    /// Whether the response to each question was accepted, indexed like `questions`. Not
+    /// saved, as it only belongs to the questions it was graded against.
+    #[serde(skip)]
+    pub results: Vec<Option<bool>>,
+}
+
+impl Quiz {
+    pub fn new(questions: Vec<Question>) -> Self {
+        let results = vec![None; questions.len()];
+        Self {
+            questions,
+            results,
+            ..Self::default()
+        }
+    }
+
+    pub fn current(&self) -> Option<&Question> {
+        self.questions.get(self.question_nr)
+    }
+
+    /// The nearest question after the current one, or before it, that passes the tag filter.
+    pub fn neighbor(&self, forward: bool) -> Option<usize> {
+        let from = self.question_nr;
+        let passes = |&i: &usize| self.passes_filter(i);
+        match forward {
+            true => (from.saturating_add(1)..self.questions.len()).find(passes),
+            false => (0..from.min(self.questions.len())).rev().find(passes),
+        }
+    }
+
+    /// The first question that passes the tag filter.
+    pub fn first(&self) -> Option<usize> {
+        (0..self.questions.len()).find(|&i| self.passes_filter(i))
+    }
+
+    fn passes_filter(&self, nr: usize) -> bool {
+        self.tag_filter.matches(&self.questions[nr].tags)
+    }
+
+    /// Moves to the next question passing the tag filter. Returns `false` if there is none.
+    pub fn next_question(&mut self) -> bool {
+        self.go_to(self.neighbor(true))
+    }
+
+    /// Moves to the previous question passing the tag filter. Returns `false` if there is none.
+    pub fn prev_question(&mut self) -> bool {
+        self.go_to(self.neighbor(false))
+    }
+
+    fn go_to(&mut self, nr: Option<usize>) -> bool {
+        let Some(nr) = nr else {
+            return false;
+        };
+        self.question_nr = nr;
+        self.show = Show::default();
+        true
+    }
+
+    pub fn reveal(&mut self, section: Section) {
+        self.show.reveal(section);
+    }
+
+    /// See [`Show::reveal_letter`].
+    pub fn reveal_letter(&mut self) {
+        if let Some(question) = self.questions.get(self.question_nr) {
+            self.show.reveal_letter(&question.answer);
+        }
+    }
+
+    /// Grades `response` to the current question with [`Self::grade`] and reveals the answer.
+    /// Returns whether it was accepted.
+    pub fn answer(&mut self, response: &str) -> bool {
+        if self.current().is_none() {
+            return false;
+        }
+        let correct = self.grade(self.question_nr, response);
+        self.show.answer = true;
+        correct
+    }
+
+    /// Grades `response` to question `nr` with [`Question::accepts`] and records the result.
+    pub fn grade(&mut self, nr: usize, response: &str) -> bool {
+        let Some(question) = self.questions.get(nr) else {
+            return false;
+        };
+        let correct = question.accepts(response);
+        self.results.resize(self.questions.len(), None);
+        self.results[nr] = Some(correct);
+        correct
+    }
+
+    /// How many questions were answered correctly.
+    pub fn score(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|&&result| result == Some(true))
+            .count()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Three capitals, two of them tagged "Europa".
+    pub(crate) fn quiz() -> Quiz {
+        Quiz::new(vec![
+            Question::new("Hauptstadt von Frankreich?", "Paris").with_tag("Europa"),
+            Question::new("Hauptstadt von Japan?", "Tokio").with_tag("Asien"),
+            Question::new("Hauptstadt von Italien?", "Rom").with_tag("Europa"),
+        ])
+    }
+
+    #[test]
+    fn next_and_prev_stop_at_the_ends() {
+        let mut quiz = quiz();
+        assert!(!quiz.prev_question());
+        assert!(quiz.next_question());
+        assert!(quiz.next_question());
+        assert_eq!(quiz.question_nr, 2);
+        assert!(!quiz.next_question());
+        assert_eq!(quiz.question_nr, 2);
+        assert!(quiz.prev_question());
+        assert_eq!(quiz.question_nr, 1);
+    }
+
+    #[test]
+    fn navigation_skips_filtered_questions_and_hides_reveals() {
+        let mut quiz = quiz();
+        quiz.tag_filter.selected.insert("Europa".to_owned());
+        quiz.reveal(Section::Answer);
+        assert!(quiz.next_question());
+        assert_eq!(quiz.question_nr, 2);
+        assert!(!quiz.show.answer);
+        assert!(quiz.prev_question());
+        assert_eq!(quiz.question_nr, 0);
+    }
+
+    #[test]
+    fn answer_grades_and_score_counts() {
+        let mut quiz = quiz();
+        assert!(quiz.answer(" paris "));
+        assert!(quiz.show.answer);
+        quiz.next_question();
+        assert!(!quiz.answer("Kyoto"));
+        quiz.next_question();
+        assert!(quiz.answer("Rom"));
+        assert_eq!(quiz.results, [Some(true), Some(false), Some(true)]);
+        assert_eq!(quiz.score(), 2);
+    }
+}
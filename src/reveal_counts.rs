@@ -0,0 +1,73 @@
+use crate::quiz_core::Question;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How often the answer of each question had to be revealed, across sessions.
+///
+/// Counts are kept per quiz, keyed by [`RevealCounts::quiz_key`], and per question, keyed by
+/// the hash of its text, so they survive reordering the file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RevealCounts {
+    quizzes: HashMap<u64, HashMap<u64, u32>>,
+}
+
+impl RevealCounts {
+    /// Identifies a quiz by the text of its questions, in any order.
+    pub fn quiz_key(questions: &[Question]) -> u64 {
+        let mut hashes: Vec<u64> = questions
+            .iter()
+            .map(|q| crate::stable_hash(&q.question))
+            .collect();
+        hashes.sort_unstable();
+        let hashes: Vec<String> = hashes.iter().map(u64::to_string).collect();
+        crate::stable_hash(&hashes.join(","))
+    }
+
+    pub fn get(&self, quiz: u64, question: &Question) -> u32 {
+        self.quizzes
+            .get(&quiz)
+            .and_then(|counts| counts.get(&crate::stable_hash(&question.question)))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    pub fn record(&mut self, quiz: u64, question: &Question) {
+        let key = crate::stable_hash(&question.question);
+        *self
+            .quizzes
+            .entry(quiz)
+            .or_default()
+            .entry(key)
+            .or_default() += 1;
+    }
+
+    /// Forgets the counts of one quiz.
+    pub fn clear(&mut self, quiz: u64) {
+        self.quizzes.remove(&quiz);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quiz_key_ignores_order() {
+        let questions = vec![
+            Question::new("Hauptstadt von Frankreich?", "Paris"),
+            Question::new("Hauptstadt von Italien?", "Rom"),
+            Question::new("Hauptstadt von Spanien?", "Madrid"),
+        ];
+        let mut shuffled = questions.clone();
+        shuffled.reverse();
+        assert_eq!(
+            RevealCounts::quiz_key(&questions),
+            RevealCounts::quiz_key(&shuffled)
+        );
+        assert_ne!(
+            RevealCounts::quiz_key(&questions),
+            RevealCounts::quiz_key(&questions[1..])
+        );
+    }
+}
@@ -0,0 +1,27 @@
+use egui::{ColorImage, Rect};
+
+/// The part of `screenshot` inside `rect`, given in points, encoded as PNG.
+pub fn png_of_region(
+    screenshot: &ColorImage,
+    rect: Rect,
+    pixels_per_point: f32,
+) -> Result<Vec<u8>, String> {
+    let [width, height] = screenshot.size;
+    let screen = Rect::from_min_size(
+        egui::Pos2::ZERO,
+        egui::vec2(width as f32, height as f32) / pixels_per_point,
+    );
+    let rect = rect.intersect(screen);
+    if !rect.is_positive() {
+        return Err("Der Bereich ist leer".to_owned());
+    }
+    let region = screenshot.region(&rect, Some(pixels_per_point));
+    let [width, height] = region.size;
+    let image = image::RgbaImage::from_raw(width as u32, height as u32, region.as_raw().to_vec())
+        .ok_or("Das Bild hat die falsche Größe")?;
+    let mut png = Vec::new();
+    image
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .map_err(|err| err.to_string())?;
+    Ok(png)
+}
@@ -0,0 +1,27 @@
+use crate::quiz_core::{Question, Show, TagFilter};
+use serde::{Deserialize, Serialize};
+
+/// The working state of a quiz in one file, to continue it on another machine.
+///
+/// Unlike an export of the quiz, this also keeps the position, what was revealed and the tag
+/// filter. The settings of the app are not part of it.
+#[derive(Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    pub questions: Vec<Question>,
+    pub question_nr: usize,
+    /// What is revealed of the current question.
+    pub show: Show,
+    /// What was revealed of every question, indexed like `questions`.
+    pub reveals: Vec<Option<Show>>,
+    pub tag_filter: TagFilter,
+}
+
+impl SessionSnapshot {
+    pub fn to_json(&self) -> Vec<u8> {
+        serde_json::to_vec_pretty(self).expect("a session can always be serialized")
+    }
+
+    pub fn from_json(bytes: &[u8]) -> Result<Self, String> {
+        serde_json::from_slice(bytes).map_err(|err| format!("keine gültige Sitzung: {err}"))
+    }
+}
@@ -0,0 +1,49 @@
+use crate::quiz_core::Question;
+use std::collections::BTreeMap;
+
+/// Puts `questions` into a random order.
+pub fn shuffle(questions: &mut [Question]) {
+    fastrand::shuffle(questions);
+}
+
+/// Puts `questions` into a random order in which questions of the same topic are spread out
+/// as evenly as possible. The topic of a question is its first tag.
+///
+/// The questions are grouped by topic and each group is shuffled. Then the order is built one
+/// question at a time, always taking from the group with the most questions left other than
+/// the group taken from just before. Ties are broken at random. Taking from the largest group
+/// first keeps it from piling up at the end, so two questions of the same topic only follow
+/// each other when one topic makes up more than half of the quiz.
+pub fn shuffle_spread_topics(questions: &mut Vec<Question>) {
+    let mut groups: BTreeMap<Option<String>, Vec<Question>> = BTreeMap::new();
+    for question in questions.drain(..) {
+        let topic = question.tags.first().cloned();
+        groups.entry(topic).or_default().push(question);
+    }
+    let mut groups: Vec<Vec<Question>> = groups.into_values().collect();
+    for group in &mut groups {
+        fastrand::shuffle(group);
+    }
+    fastrand::shuffle(&mut groups);
+
+    let mut last = None;
+    loop {
+        let others: Vec<usize> = (0..groups.len())
+            .filter(|&i| Some(i) != last && !groups[i].is_empty())
+            .collect();
+        let Some(most) = others.iter().map(|&i| groups[i].len()).max() else {
+            // Only the group placed last has questions left, so they have to follow each other.
+            if let Some(last) = last {
+                questions.append(&mut groups[last]);
+            }
+            break;
+        };
+        let largest: Vec<usize> = others
+            .into_iter()
+            .filter(|&i| groups[i].len() == most)
+            .collect();
+        let next = largest[fastrand::usize(..largest.len())];
+        questions.extend(groups[next].pop());
+        last = Some(next);
+    }
+}
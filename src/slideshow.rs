@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+/// Advances to the next question automatically, e.g. for an unattended display board.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SlideshowConfig {
+    /// Seconds each question stays on screen.
+    pub interval: f32,
+    /// Reveal the answer this many seconds before advancing. `0` keeps it hidden.
+    pub reveal_answer: f32,
+}
+
+impl Default for SlideshowConfig {
+    fn default() -> Self {
+        Self {
+            interval: 20.0,
+            reveal_answer: 5.0,
+        }
+    }
+}
+
+impl SlideshowConfig {
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Sekunden pro Frage:");
+            ui.add(egui::DragValue::new(&mut self.interval).range(1.0..=3600.0));
+        });
+        ui.horizontal(|ui| {
+            ui.label("Antwort zeigen (Sekunden vorher):");
+            ui.add(egui::DragValue::new(&mut self.reveal_answer).range(0.0..=self.interval));
+        });
+    }
+}
@@ -0,0 +1,47 @@
+use crate::images::is_image_ref;
+use crate::quiz_core::Question;
+
+/// Reading speed used for the time estimate, in words per minute.
+const WORDS_PER_MINUTE: usize = 200;
+
+/// How long a quiz is, for planning a lesson around it.
+#[derive(Debug, Clone, Copy)]
+pub struct QuizStats {
+    pub questions: usize,
+    /// Words in the questions, hints and answers. Image hints don't count.
+    pub words: usize,
+}
+
+impl QuizStats {
+    pub fn new(questions: &[Question]) -> Self {
+        let words = questions
+            .iter()
+            .flat_map(|q| [&q.question, &q.hint1, &q.hint2, &q.answer])
+            .filter(|text| !is_image_ref(text))
+            .map(|text| text.split_whitespace().count())
+            .sum();
+        Self {
+            questions: questions.len(),
+            words,
+        }
+    }
+
+    /// Estimated time to read everything aloud, rounded up to whole minutes.
+    pub fn reading_minutes(&self) -> usize {
+        self.words.div_ceil(WORDS_PER_MINUTE)
+    }
+
+    pub fn ui(&self, ui: &mut egui::Ui) {
+        egui::Grid::new("quiz_stats_grid").show(ui, |ui| {
+            ui.label("Fragen:");
+            ui.label(self.questions.to_string());
+            ui.end_row();
+            ui.label("Wörter:");
+            ui.label(self.words.to_string());
+            ui.end_row();
+            ui.label("Lesezeit:");
+            ui.label(format!("ca. {} min", self.reading_minutes()));
+            ui.end_row();
+        });
+    }
+}
@@ -0,0 +1,82 @@
+use crate::quiz_core::{TagFilter, TagMatch};
+use std::collections::BTreeSet;
+
+impl TagFilter {
+    /// Checkboxes for every tag in `available`, plus the any/all switch.
+    pub fn ui(&mut self, ui: &mut egui::Ui, available: &BTreeSet<&str>) {
+        if available.is_empty() {
+            ui.label("Dieses Quiz hat keine Tags.");
+            return;
+        }
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut self.mode, TagMatch::Any, "Irgendein Tag");
+            ui.radio_value(&mut self.mode, TagMatch::All, "Alle Tags");
+        });
+        ui.separator();
+        for &tag in available {
+            let mut checked = self.selected.contains(tag);
+            if ui.checkbox(&mut checked, tag).changed() {
+                if checked {
+                    self.selected.insert(tag.to_owned());
+                } else {
+                    self.selected.remove(tag);
+                }
+            }
+        }
+        ui.separator();
+        if ui.button("Filter zurücksetzen").clicked() {
+            self.selected.clear();
+        }
+    }
+}
+
+/// `tag` and every level above it, e.g. `"Biologie"` and `"Biologie / Zellbiologie"`.
+pub fn tag_levels(tag: &str) -> impl Iterator<Item = &str> {
+    tag.match_indices('/')
+        .map(|(i, _)| tag[..i].trim_end())
+        .chain(std::iter::once(tag))
+}
+
+/// The hierarchical tags among `tags` as a row of links, one per level. Clicking a level
+/// filters to it.
+pub fn tag_breadcrumbs(ui: &mut egui::Ui, tags: &[String], filter: &mut TagFilter) {
+    for tag in tags.iter().filter(|tag| tag.contains('/')) {
+        ui.horizontal(|ui| {
+            for (i, level) in tag_levels(tag).enumerate() {
+                if i > 0 {
+                    ui.weak("›");
+                }
+                let name = level.rsplit('/').next().unwrap_or(level).trim();
+                let tooltip = format!("Nur „{level}“ zeigen");
+                if ui.link(name).on_hover_text(tooltip).clicked() {
+                    filter.selected = BTreeSet::from([level.to_owned()]);
+                }
+            }
+        });
+    }
+}
+
+/// Renders `tags` as a row of colored chips.
+pub fn tag_chips(ui: &mut egui::Ui, tags: &[String]) {
+    if tags.is_empty() {
+        return;
+    }
+    ui.horizontal_wrapped(|ui| {
+        for tag in tags {
+            egui::Frame::none()
+                .fill(tag_color(tag))
+                .rounding(4.0)
+                .inner_margin(egui::Margin::symmetric(4.0, 1.0))
+                .show(ui, |ui| {
+                    ui.label(egui::RichText::new(tag).small().color(egui::Color32::BLACK));
+                });
+        }
+    });
+}
+
+/// A color derived from the tag name, so a tag looks the same in every quiz and session.
+pub fn tag_color(tag: &str) -> egui::Color32 {
+    let hash = crate::stable_hash(tag);
+    let hue = (hash % 360) as f32 / 360.0;
+    egui::ecolor::Hsva::new(hue, 0.45, 0.9, 1.0).into()
+}
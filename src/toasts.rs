@@ -0,0 +1,69 @@
+/// Seconds a toast stays on screen.
+const TOAST_SECS: f64 = 4.0;
+
+struct Toast {
+    text: String,
+    error: bool,
+    /// `egui::InputState::time` when it was shown.
+    shown_at: f64,
+}
+
+/// Short notices in the bottom right corner that disappear on their own, e.g. after saving.
+#[derive(Default)]
+pub struct Toasts {
+    toasts: Vec<Toast>,
+}
+
+impl Toasts {
+    pub fn success(&mut self, ctx: &egui::Context, text: impl Into<String>) {
+        self.push(ctx, text.into(), false);
+    }
+
+    pub fn error(&mut self, ctx: &egui::Context, text: impl Into<String>) {
+        self.push(ctx, text.into(), true);
+    }
+
+    fn push(&mut self, ctx: &egui::Context, text: String, error: bool) {
+        let shown_at = ctx.input(|i| i.time);
+        self.toasts.push(Toast {
+            text,
+            error,
+            shown_at,
+        });
+        ctx.request_repaint();
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        let now = ctx.input(|i| i.time);
+        self.toasts
+            .retain(|toast| now - toast.shown_at < TOAST_SECS);
+        let Some(oldest) = self.toasts.first() else {
+            return;
+        };
+        ctx.request_repaint_after_secs((oldest.shown_at + TOAST_SECS - now) as f32);
+
+        let mut dismissed = None;
+        egui::Area::new(egui::Id::new("toasts"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-8.0, -8.0))
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                for (i, toast) in self.toasts.iter().enumerate() {
+                    let visuals = ui.visuals();
+                    let (fill, text_color) = match toast.error {
+                        true => (visuals.error_fg_color, egui::Color32::WHITE),
+                        false => (visuals.extreme_bg_color, visuals.text_color()),
+                    };
+                    let frame = egui::Frame::popup(ui.style()).fill(fill);
+                    let response = frame
+                        .show(ui, |ui| ui.colored_label(text_color, &toast.text))
+                        .response;
+                    if response.interact(egui::Sense::click()).clicked() {
+                        dismissed = Some(i);
+                    }
+                }
+            });
+        if let Some(i) = dismissed {
+            self.toasts.remove(i);
+        }
+    }
+}